@@ -0,0 +1,67 @@
+//! Parser throughput benchmark.
+//!
+//! Run with `cargo bench`. Each input is parsed repeatedly and the average time per parse is printed.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const PAIRS: &[&str] = &[
+    r#"40.446° N 79.982° W"#,
+    r#"N 40° 26.767' W 79° 58.933'"#,
+    r#"40° 26′ 46″ N 79° 58′ 56″ W"#,
+    r#"40.446, -79.982"#,
+    r#"N 40° 26′ 46″, W 79° 58′ 56″"#,
+    r#"40° 26′ 46″ 79° 58′ 56″"#,
+    r#"40° 26.767' N 79° 58.933' W"#,
+    r#"40° 26.767', 79° 58.933'"#,
+    r#"N 40.446° W 79.982°"#,
+    r#"not a coordinate"#,
+];
+
+const SINGLE: &[&str] = &[
+    r#"40.446° N"#,
+    r#"N 40° 26.767'"#,
+    r#"40° 26′ 46″ N"#,
+    r#"-40.446"#,
+    r#"N 40° 26′ 46″"#,
+    r#"40° 26′ 46″"#,
+    r#"40° 26.767' N"#,
+    r#"40° 26.767'"#,
+    r#"N 40.446°"#,
+    r#"not a coordinate"#,
+];
+
+const ROUNDS: usize = 100_000;
+
+fn run<F: Fn(&str) -> bool>(name: &str, inputs: &[&str], f: F) {
+    // warm up (also initializes any lazy state)
+    for s in inputs {
+        black_box(f(black_box(s)));
+    }
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for s in inputs {
+            black_box(f(black_box(s)));
+        }
+    }
+    let elapsed = start.elapsed();
+
+    let count = (ROUNDS * inputs.len()) as u32;
+    let per_parse = elapsed / count;
+    println!(
+        "{:<12} {:>8} ns/parse {:>12.0} parses/s",
+        name,
+        per_parse.as_nanos(),
+        f64::from(count) / elapsed.as_secs_f64().max(Duration::from_nanos(1).as_secs_f64())
+    );
+}
+
+fn main() {
+    run("parse", PAIRS, |s| latlon::parse(s).is_ok());
+    run("parse_lat", SINGLE, |s| latlon::parse_lat(s).is_ok());
+    run("parse_lng", SINGLE, |s| latlon::parse_lng(s).is_ok());
+
+    let opts = latlon::ParseOptions::default();
+    run("parse_str", PAIRS, |s| latlon::parse_str(s, &opts).is_ok());
+}
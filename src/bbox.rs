@@ -0,0 +1,186 @@
+//! Bounding boxes, given as four numbers (`14.2,49.9,14.7,50.2`) or two corners
+
+use crate::{grid, parse_str, GeoParseError, ParseOptions, Point};
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use geo_types::{coord, Rect};
+
+/// Order of the numbers of a bounding box
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum AxisOrder {
+    /// Longitude first: west, south, east, north (GeoJSON, OpenStreetMap, WMS 1.1)
+    #[default]
+    LngLat,
+    /// Latitude first: south, west, north, east (Leaflet, Overpass, WMS 1.3 with EPSG:4326)
+    LatLng,
+}
+
+/// Parse a bounding box.
+///
+/// Supported formats:
+/// - four numbers in the axis order, e.g. `14.2,49.9,14.7,50.2` (`AxisOrder::LngLat`), optionally
+///   prefixed by `bbox=`, and separated by commas or whitespace
+/// - the numbers as arrays, e.g. `[14.2,49.9,14.7,50.2]` or `[[49.9,14.2],[50.2,14.7]]` (`AxisOrder::LatLng`)
+/// - two corners in any format supported by `parse()`, e.g. `N 49°54' E 14°12', N 50°12' E 14°42'`,
+///   separated by comma, semicolon, slash, a dash or whitespace. The axis order does not apply to them.
+///
+/// The first longitude is the west edge, and the second the east edge. When the west edge is greater,
+/// the box crosses the antimeridian and the east edge of the returned `Rect` is moved by 360° (e.g.
+/// `170,-10,-170,10` spans 170° to 190°). Latitudes can come in any order.
+///
+/// ```
+/// use latlon::{parse_bbox, AxisOrder};
+///
+/// let rect = parse_bbox("bbox=14.2,49.9,14.7,50.2", AxisOrder::LngLat).unwrap();
+/// assert_eq!(rect, parse_bbox("[[49.9,14.2],[50.2,14.7]]", AxisOrder::LatLng).unwrap());
+/// assert_eq!((14.2, 49.9), rect.min().x_y());
+/// ```
+pub fn parse_bbox<T: AsRef<str> + Display>(text: T, order: AxisOrder) -> Result<Rect<f64>, GeoParseError<T>> {
+    match do_parse_bbox(text.as_ref(), order) {
+        Some(r) => Ok(r),
+        None => Err(GeoParseError(text)),
+    }
+}
+
+/// Format a bounding box as four numbers in the axis order, see `BboxDisplay`
+pub fn format_bbox(rect: Rect<f64>, order: AxisOrder) -> BboxDisplay {
+    BboxDisplay { rect, order }
+}
+
+/// Bounding box formatter, returned by `format_bbox()`.
+///
+/// The `Display` implementation writes the four numbers separated by commas, e.g. `14.2,49.9,14.7,50.2`.
+/// Precision (`{:.5}`) applies to all numbers. The alternate form (`{:#}`) writes the two corners as
+/// arrays, e.g. `[[14.2,49.9],[14.7,50.2]]`. East edges beyond 180° are written as negative longitude,
+/// so a box crossing the antimeridian can be parsed back by `parse_bbox()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BboxDisplay {
+    rect: Rect<f64>,
+    order: AxisOrder,
+}
+
+impl Display for BboxDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (min, max) = (self.rect.min(), self.rect.max());
+        let east = if max.x > 180. { max.x - 360. } else { max.x };
+        let (a, b) = match self.order {
+            AxisOrder::LngLat => ((min.x, min.y), (east, max.y)),
+            AxisOrder::LatLng => ((min.y, min.x), (max.y, east)),
+        };
+
+        let precision = f.precision();
+        let num = |f: &mut Formatter<'_>, v: f64| match precision {
+            Some(precision) => write!(f, "{:.*}", precision, v),
+            None => write!(f, "{}", v),
+        };
+
+        if f.alternate() {
+            f.write_str("[[")?;
+            num(f, a.0)?;
+            f.write_str(",")?;
+            num(f, a.1)?;
+            f.write_str("],[")?;
+            num(f, b.0)?;
+            f.write_str(",")?;
+            num(f, b.1)?;
+            f.write_str("]]")
+        } else {
+            num(f, a.0)?;
+            f.write_str(",")?;
+            num(f, a.1)?;
+            f.write_str(",")?;
+            num(f, b.0)?;
+            f.write_str(",")?;
+            num(f, b.1)
+        }
+    }
+}
+
+/// Build the rectangle from edges, checking their range
+fn build_rect(west: f64, south: f64, east: f64, north: f64) -> Option<Rect<f64>> {
+    let lat_ok = |v: f64| (-90f64..=90f64).contains(&v);
+    let lng_ok = |v: f64| (-180f64..=180f64).contains(&v);
+    if !(lat_ok(south) && lat_ok(north) && lng_ok(west) && lng_ok(east)) {
+        return None;
+    }
+
+    let east = if west > east { east + 360. } else { east };
+    Some(Rect::new(coord! { x: west, y: south }, coord! { x: east, y: north }))
+}
+
+/// Strip the optional `bbox=` prefix
+fn strip_key(s: &str) -> &str {
+    let s = s.trim();
+    match s.get(..5) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("bbox=") => &s[5..],
+        _ => s,
+    }
+}
+
+/// Split numbers separated by commas, whitespace or array brackets
+fn number_parts(s: &str) -> impl Iterator<Item = &str> + Clone {
+    s.split(|c: char| c == ',' || c == '[' || c == ']' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+}
+
+/// Parse four numbers in the axis order
+fn parse_numbers(s: &str, order: AxisOrder) -> Option<Rect<f64>> {
+    let mut numbers = [0f64; 4];
+    let mut count = 0;
+    for part in number_parts(s) {
+        *numbers.get_mut(count)? = grid::parse_decimal(part)?;
+        count += 1;
+    }
+    if count != 4 {
+        return None;
+    }
+
+    let [a, b, c, d] = numbers;
+    match order {
+        AxisOrder::LngLat => build_rect(a, b.min(d), c, b.max(d)),
+        AxisOrder::LatLng => build_rect(b, a.min(c), d, a.max(c)),
+    }
+}
+
+/// Parse two corners, trying every separator. All splits that give two valid corners must agree.
+fn parse_corners(s: &str) -> Option<Rect<f64>> {
+    let opts = ParseOptions::default();
+    let s = s.trim();
+
+    let mut found: Option<Rect<f64>> = None;
+    for (i, c) in s.char_indices() {
+        if !(c == ',' || c == ';' || c == '/' || c == '-' || c.is_whitespace()) {
+            continue;
+        }
+
+        let (first, second) = (&s[..i], &s[i + c.len_utf8()..]);
+        // a dash is a separator only when surrounded by whitespace, otherwise it is a minus sign
+        if c == '-' && !(first.ends_with(char::is_whitespace) && second.starts_with(char::is_whitespace)) {
+            continue;
+        }
+
+        let corners = (parse_str(first, &opts), parse_str(second, &opts));
+        if let (Ok(a), Ok(b)) = corners {
+            let rect = corner_rect(a, b)?;
+            if matches!(found, Some(r) if r != rect) {
+                return None;
+            }
+            found = Some(rect);
+        }
+    }
+    found
+}
+
+fn corner_rect(a: Point<f64>, b: Point<f64>) -> Option<Rect<f64>> {
+    build_rect(a.x(), a.y().min(b.y()), b.x(), a.y().max(b.y()))
+}
+
+fn do_parse_bbox(s: &str, order: AxisOrder) -> Option<Rect<f64>> {
+    let numbers = strip_key(s);
+    // plain numbers are never taken as corners, which would ignore the axis order
+    if number_parts(numbers).all(|part| grid::parse_decimal(part).is_some()) {
+        parse_numbers(numbers, order)
+    } else {
+        parse_corners(s)
+    }
+}
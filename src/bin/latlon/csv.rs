@@ -0,0 +1,236 @@
+//! Conversion of coordinate columns in CSV / TSV files.
+//!
+//! Records are read and written one at a time, so files of any size can be processed.
+
+use std::io;
+use std::io::{BufRead, Write};
+
+/// Column selected by name (from the header) or 1-based index
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnRef(pub String);
+
+impl ColumnRef {
+    /// Find the column index, preferring a header name over a number
+    fn resolve(&self, header: Option<&[String]>) -> Result<usize, String> {
+        if let Some(i) = header.and_then(|h| h.iter().position(|name| name.trim() == self.0)) {
+            return Ok(i);
+        }
+
+        match self.0.parse::<usize>() {
+            Ok(n) if n > 0 => Ok(n - 1),
+            _ => Err(format!("Unknown column: {}", self.0)),
+        }
+    }
+}
+
+/// Which columns hold the coordinates
+#[derive(Debug, Clone)]
+pub enum Columns {
+    /// One column with both coordinates, replaced by latitude and longitude columns
+    Pair(ColumnRef),
+    /// Latitude and longitude columns, converted in place
+    LatLng(ColumnRef, ColumnRef),
+}
+
+/// CSV conversion settings
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Field delimiter, `,` or `\t`
+    pub delimiter: u8,
+    /// The first record is a header
+    pub header: bool,
+    pub columns: Columns,
+    /// Decimal places of the output
+    pub precision: Option<usize>,
+}
+
+/// Reader of delimited records with quoting as in RFC 4180 (quoted fields may contain
+/// the delimiter, doubled quotes and line breaks)
+struct Reader<R> {
+    input: R,
+    delimiter: char,
+    line: String,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Read the next record to `fields`. Returns false at the end of input.
+    fn read_record(&mut self, fields: &mut Vec<String>) -> io::Result<bool> {
+        fields.clear();
+
+        self.line.clear();
+        if self.input.read_line(&mut self.line)? == 0 {
+            return Ok(false);
+        }
+
+        let mut field = String::new();
+        let mut quoted = false;
+        loop {
+            let mut chars = self.line.trim_end_matches(['\n', '\r']).chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' if quoted && chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' if quoted => quoted = false,
+                    '"' if field.is_empty() => quoted = true,
+                    c if c == self.delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+                    c => field.push(c),
+                }
+            }
+
+            if !quoted {
+                break;
+            }
+
+            // line break inside a quoted field
+            field.push('\n');
+            self.line.clear();
+            if self.input.read_line(&mut self.line)? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Unterminated quoted field"));
+            }
+        }
+
+        fields.push(field);
+        Ok(true)
+    }
+}
+
+/// Write a record, quoting fields where needed
+fn write_record(out: &mut impl Write, fields: &[String], delimiter: u8) -> io::Result<()> {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.write_all(&[delimiter])?;
+        }
+
+        let needs_quotes = field.bytes().any(|b| b == delimiter || b == b'"' || b == b'\n' || b == b'\r');
+        if needs_quotes {
+            write!(out, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            out.write_all(field.as_bytes())?;
+        }
+    }
+    out.write_all(b"\n")
+}
+
+/// Format a converted value
+fn format_value(value: f64, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{:.*}", p, value),
+        None => value.to_string(),
+    }
+}
+
+/// Convert the coordinate columns of one record. Empty cells are left empty.
+fn convert_record(fields: &mut Vec<String>, columns: &[usize], opts: &CsvOptions) -> Result<(), String> {
+    if let Some(&missing) = columns.iter().find(|&&i| i >= fields.len()) {
+        return Err(format!("Missing column {}", missing + 1));
+    }
+
+    match columns {
+        [i] => {
+            let (lat, lng) = match fields[*i].trim() {
+                "" => (String::new(), String::new()),
+                cell => {
+                    let point = latlon::parse(cell).map_err(|e| e.to_string())?;
+                    (format_value(point.y(), opts.precision), format_value(point.x(), opts.precision))
+                }
+            };
+            fields[*i] = lat;
+            fields.insert(*i + 1, lng);
+        }
+        [lat, lng] => {
+            let lat_value = match fields[*lat].trim() {
+                "" => None,
+                cell => Some(latlon::parse_lat(cell).map_err(|e| e.to_string())?),
+            };
+            let lng_value = match fields[*lng].trim() {
+                "" => None,
+                cell => Some(latlon::parse_lng(cell).map_err(|e| e.to_string())?),
+            };
+
+            if let Some(value) = lat_value {
+                fields[*lat] = format_value(value, opts.precision);
+            }
+            if let Some(value) = lng_value {
+                fields[*lng] = format_value(value, opts.precision);
+            }
+        }
+        _ => unreachable!("one or two coordinate columns"),
+    }
+
+    Ok(())
+}
+
+/// Convert a CSV / TSV stream. Errors are reported to `errors` with the row number
+/// (counting the header), and the row is written unchanged.
+///
+/// Returns false if any row failed.
+pub fn convert(input: impl BufRead, out: &mut impl Write, errors: &mut impl Write, opts: &CsvOptions) -> io::Result<bool> {
+    let mut reader = Reader {
+        input,
+        delimiter: char::from(opts.delimiter),
+        line: String::new(),
+    };
+
+    let mut fields = Vec::new();
+    let mut row = 0;
+    let mut ok = true;
+
+    let header = if opts.header {
+        if !reader.read_record(&mut fields)? {
+            return Ok(true);
+        }
+        row += 1;
+        Some(fields.clone())
+    } else {
+        None
+    };
+
+    let invalid = |e| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let columns = match &opts.columns {
+        Columns::Pair(c) => vec![c.resolve(header.as_deref()).map_err(invalid)?],
+        Columns::LatLng(lat, lng) => vec![
+            lat.resolve(header.as_deref()).map_err(invalid)?,
+            lng.resolve(header.as_deref()).map_err(invalid)?,
+        ],
+    };
+
+    if let Some(mut header) = header {
+        if let [i] = columns[..] {
+            if i >= header.len() {
+                return Err(invalid(format!("Missing column {}", i + 1)));
+            }
+            let name = header[i].trim().to_string();
+            header[i] = format!("{}_lat", name);
+            header.insert(i + 1, format!("{}_lon", name));
+        }
+        write_record(out, &header, opts.delimiter)?;
+    }
+
+    while reader.read_record(&mut fields)? {
+        row += 1;
+
+        // skip blank lines
+        if fields.len() == 1 && fields[0].is_empty() {
+            continue;
+        }
+
+        let original = fields.clone();
+        if let Err(e) = convert_record(&mut fields, &columns, opts) {
+            writeln!(errors, "row {}: {}", row, e)?;
+            ok = false;
+
+            // keep the row, with an empty longitude column in the single column mode
+            fields = original;
+            if let [i] = columns[..] {
+                fields.resize(fields.len().max(i + 1), String::new());
+                fields.insert(i + 1, String::new());
+            }
+        }
+
+        write_record(out, &fields, opts.delimiter)?;
+    }
+
+    Ok(ok)
+}
@@ -0,0 +1,329 @@
+//! Command-line tool converting coordinates between notations.
+//!
+//! Coordinates are taken from the arguments, or from standard input (one per line),
+//! parsed with `latlon::parse()`, and printed in the selected output style.
+//! In the CSV mode, coordinate columns of a CSV / TSV file are converted to decimal degrees.
+
+mod csv;
+#[cfg(test)]
+mod tests;
+
+use crate::csv::{ColumnRef, Columns, CsvOptions};
+use latlon::{Geohash, LatLon, Mgrs, Point, Ups, Utm};
+use std::env;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::process;
+
+/// Largest accepted `--precision`, beyond the digits of `f64`
+const MAX_PRECISION: usize = 15;
+
+const USAGE: &str = "\
+Usage: latlon [OPTIONS] [COORDINATES]...
+       latlon --csv|--tsv (--column COL | --lat COL --lon COL) [OPTIONS] [FILE]
+
+Convert coordinates between notations. Each argument is one coordinate pair
+(quote it if it contains spaces). Without arguments, coordinates are read from
+standard input, one per line.
+
+In the CSV mode, coordinate columns of a CSV or TSV file (or standard input)
+are converted to decimal degrees. A column holding both coordinates is replaced
+by two columns, NAME_lat and NAME_lon; separate latitude and longitude columns
+are converted in place. Columns are selected by header name or by number
+(from 1). Empty cells stay empty.
+
+Options:
+  -f, --format STYLE   Output style (default: decimal):
+                         decimal   40.446,-79.982
+                         dm        40° 26.7600′ N, 79° 58.9200′ W
+                         dms       40° 26′ 45.60″ N, 79° 58′ 55.20″ W
+                         utm       17T 586329 4477758 (UPS in polar regions)
+                         mgrs      17TNE8632877758
+                         geohash   dppn5fyzh
+                         wkt       POINT(-79.982 40.446)
+                         geojson   {\"type\":\"Point\",\"coordinates\":[-79.982,40.446]}
+  -p, --precision N    Decimal places of degrees, minutes, seconds or meters (0-15);
+                       number of digits for mgrs (0-5), characters for geohash (1-12)
+  -h, --help           Print this help
+
+CSV options:
+      --csv            Convert a comma-separated file
+      --tsv            Convert a tab-separated file
+      --column COL     Column with both coordinates
+      --lat COL        Column with latitude
+      --lon COL        Column with longitude
+      --no-header      The file has no header row (select columns by number)
+
+Lines (or CSV rows) that cannot be converted are reported on standard error
+with their number, and the exit status is 1. Failed CSV rows are written
+unchanged.";
+
+/// Output style
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+    Decimal,
+    Dm,
+    Dms,
+    Utm,
+    Mgrs,
+    Geohash,
+    Wkt,
+    GeoJson,
+}
+
+impl Style {
+    fn from_name(name: &str) -> Option<Style> {
+        match name.to_ascii_lowercase().as_str() {
+            "decimal" | "dd" => Some(Style::Decimal),
+            "dm" => Some(Style::Dm),
+            "dms" => Some(Style::Dms),
+            "utm" => Some(Style::Utm),
+            "mgrs" => Some(Style::Mgrs),
+            "geohash" => Some(Style::Geohash),
+            "wkt" => Some(Style::Wkt),
+            "geojson" => Some(Style::GeoJson),
+            _ => None,
+        }
+    }
+
+    /// Precision used if not given in the arguments
+    fn default_precision(self) -> Option<usize> {
+        match self {
+            Style::Dm => Some(4),
+            Style::Dms => Some(2),
+            Style::Utm => Some(0),
+            Style::Mgrs => Some(5),
+            Style::Geohash => Some(9),
+            Style::Decimal | Style::Wkt | Style::GeoJson => None,
+        }
+    }
+}
+
+/// Command-line arguments
+#[derive(Debug)]
+struct Args {
+    style: Style,
+    precision: Option<usize>,
+    help: bool,
+    /// Delimiter in the CSV mode
+    csv: Option<u8>,
+    column: Option<ColumnRef>,
+    lat: Option<ColumnRef>,
+    lng: Option<ColumnRef>,
+    no_header: bool,
+    coords: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        style: Style::Decimal,
+        precision: None,
+        help: false,
+        csv: None,
+        column: None,
+        lat: None,
+        lng: None,
+        no_header: false,
+        coords: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => parsed.help = true,
+            "-f" | "--format" => {
+                let name = args.next().ok_or("Missing output style")?;
+                parsed.style = Style::from_name(&name).ok_or_else(|| format!("Unknown output style: {}", name))?;
+            }
+            "-p" | "--precision" => {
+                let value = args.next().ok_or("Missing precision")?;
+                let precision = value.parse().ok().filter(|&p| p <= MAX_PRECISION);
+                parsed.precision = Some(precision.ok_or_else(|| format!("Invalid precision: {}", value))?);
+            }
+            "--csv" => parsed.csv = Some(b','),
+            "--tsv" => parsed.csv = Some(b'\t'),
+            "--column" => parsed.column = Some(ColumnRef(args.next().ok_or("Missing column")?)),
+            "--lat" => parsed.lat = Some(ColumnRef(args.next().ok_or("Missing column")?)),
+            "--lon" | "--lng" => parsed.lng = Some(ColumnRef(args.next().ok_or("Missing column")?)),
+            "--no-header" => parsed.no_header = true,
+            "--" => parsed.coords.extend(args.by_ref()),
+            // negative numbers are coordinates, not options
+            _ if arg.starts_with('-') && !arg[1..].starts_with(|c: char| c.is_ascii_digit()) => {
+                return Err(format!("Unknown option: {}", arg));
+            }
+            _ => parsed.coords.push(arg),
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Get the CSV mode settings, if enabled
+fn csv_options(args: &Args) -> Result<Option<CsvOptions>, String> {
+    let delimiter = match args.csv {
+        Some(delimiter) => delimiter,
+        None if args.column.is_some() || args.lat.is_some() || args.lng.is_some() => {
+            return Err("Columns can only be selected with --csv or --tsv".into())
+        }
+        None => return Ok(None),
+    };
+
+    let columns = match (&args.column, &args.lat, &args.lng) {
+        (Some(column), None, None) => Columns::Pair(column.clone()),
+        (None, Some(lat), Some(lng)) => Columns::LatLng(lat.clone(), lng.clone()),
+        _ => return Err("Select either --column, or both --lat and --lon".into()),
+    };
+
+    if args.coords.len() > 1 {
+        return Err("Only one input file can be converted".into());
+    }
+
+    Ok(Some(CsvOptions {
+        delimiter,
+        header: !args.no_header,
+        columns,
+        precision: args.precision,
+    }))
+}
+
+/// Format a coordinate as degrees and decimal minutes with hemisphere, e.g. `40° 26.7600′ N`
+fn format_dm(value: f64, positive: char, negative: char, precision: usize) -> String {
+    let hemisphere = if value < 0. { negative } else { positive };
+
+    // round to the output precision first, so that minutes never show as 60
+    let scale = 10f64.powi(precision as i32);
+    let total = (value.abs() * 60. * scale).round() / scale;
+    let degrees = (total / 60.).floor();
+    let minutes = total - degrees * 60.;
+
+    format!("{}° {:.*}′ {}", degrees, precision, minutes, hemisphere)
+}
+
+/// Format a point in the given style
+fn format_point(point: Point<f64>, style: Style, precision: Option<usize>) -> Result<String, &'static str> {
+    let ll = LatLon::from(point);
+
+    Ok(match (style, precision.or_else(|| style.default_precision())) {
+        (Style::Decimal, Some(p)) => format!("{:.*}", p, ll),
+        (Style::Decimal, None) => ll.to_string(),
+        (Style::Dm, p) => {
+            let p = p.unwrap_or_default();
+            format!("{}, {}", format_dm(ll.lat, 'N', 'S', p), format_dm(ll.lng, 'E', 'W', p))
+        }
+        (Style::Dms, Some(p)) => format!("{:#.*}", p, ll),
+        (Style::Dms, None) => format!("{:#}", ll),
+        (Style::Utm, p) => match (Utm::from_point(point), Ups::from_point(point)) {
+            (Some(utm), _) => format!("{:.*}", p.unwrap_or_default(), utm),
+            // UPS in the polar regions
+            (None, Some(ups)) => format!("{:.*}", p.unwrap_or_default(), ups),
+            (None, None) => return Err("Out of range"),
+        },
+        (Style::Mgrs, p) => {
+            let mgrs = Mgrs::from_point(point).ok_or("Outside the UTM zones (80° S to 84° N)")?;
+            if p.unwrap_or_default() > 5 {
+                return Err("MGRS precision must be 0 to 5");
+            }
+            format!("{:.*}", p.unwrap_or_default(), mgrs)
+        }
+        (Style::Geohash, p) => Geohash::encode(point, p.unwrap_or_default())
+            .ok_or("Geohash length must be 1 to 12")?
+            .to_string(),
+        (Style::Wkt, Some(p)) => format!("POINT({:.*} {:.*})", p, ll.lng, p, ll.lat),
+        (Style::Wkt, None) => format!("POINT({} {})", ll.lng, ll.lat),
+        (Style::GeoJson, Some(p)) => {
+            format!(r#"{{"type":"Point","coordinates":[{:.*},{:.*}]}}"#, p, ll.lng, p, ll.lat)
+        }
+        (Style::GeoJson, None) => format!(r#"{{"type":"Point","coordinates":[{},{}]}}"#, ll.lng, ll.lat),
+    })
+}
+
+/// Convert one input, printing the result or an error. Returns false on error.
+fn convert(out: &mut impl Write, label: &str, input: &str, args: &Args) -> io::Result<bool> {
+    let result = latlon::parse(input)
+        .map_err(|e| e.to_string())
+        .and_then(|point| format_point(point, args.style, args.precision).map_err(|e| format!("{}: {}", input, e)));
+
+    match result {
+        Ok(text) => {
+            writeln!(out, "{}", text)?;
+            Ok(true)
+        }
+        Err(e) => {
+            eprintln!("{}: {}", label, e);
+            Ok(false)
+        }
+    }
+}
+
+/// Convert a CSV / TSV file, or standard input
+fn run_csv(file: Option<&String>, opts: &CsvOptions) -> io::Result<bool> {
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    let stderr = io::stderr();
+    let mut errors = stderr.lock();
+
+    let ok = match file {
+        Some(path) => csv::convert(BufReader::new(File::open(path)?), &mut out, &mut errors, opts)?,
+        None => csv::convert(io::stdin().lock(), &mut out, &mut errors, opts)?,
+    };
+
+    out.flush()?;
+    Ok(ok)
+}
+
+fn run(args: &Args) -> io::Result<bool> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut ok = true;
+
+    if args.coords.is_empty() {
+        let stdin = io::stdin();
+        for (i, line) in stdin.lock().lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            ok &= convert(&mut out, &format!("line {}", i + 1), &line, args)?;
+        }
+    } else {
+        for (i, coord) in args.coords.iter().enumerate() {
+            ok &= convert(&mut out, &format!("argument {}", i + 1), coord, args)?;
+        }
+    }
+
+    out.flush()?;
+    Ok(ok)
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if args.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let result = match csv_options(&args) {
+        Ok(Some(opts)) => run_csv(args.coords.first(), &opts),
+        Ok(None) => run(&args),
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::csv::{convert, ColumnRef, Columns, CsvOptions};
+use crate::{format_point, parse_args, Style};
+use latlon::Point;
+
+fn run_csv(input: &str, opts: &CsvOptions) -> (String, String, bool) {
+    let mut out = Vec::new();
+    let mut errors = Vec::new();
+    let ok = convert(input.as_bytes(), &mut out, &mut errors, opts).unwrap();
+    (String::from_utf8(out).unwrap(), String::from_utf8(errors).unwrap(), ok)
+}
+
+#[test]
+fn csv_pair_column() {
+    let opts = CsvOptions {
+        delimiter: b',',
+        header: true,
+        columns: Columns::Pair(ColumnRef("position".into())),
+        precision: Some(3),
+    };
+
+    let input = "id,position,note\n\
+                 1,\"40° 26′ 46″ N, 79° 58′ 56″ W\",\"say \"\"hi\"\"\"\n\
+                 2,foo,\"multi\nline\"\n\
+                 3,,\n";
+    let (out, errors, ok) = run_csv(input, &opts);
+
+    assert_eq!(
+        "id,position_lat,position_lon,note\n\
+         1,40.446,-79.982,\"say \"\"hi\"\"\"\n\
+         2,foo,,\"multi\nline\"\n\
+         3,,,\n",
+        out
+    );
+    assert_eq!("row 3: Error parsing coordinates from foo\n", errors);
+    assert!(!ok);
+}
+
+#[test]
+fn tsv_lat_lng_columns() {
+    let opts = CsvOptions {
+        delimiter: b'\t',
+        header: false,
+        columns: Columns::LatLng(ColumnRef("2".into()), ColumnRef("3".into())),
+        precision: None,
+    };
+
+    let (out, errors, ok) = run_csv("a\t40,5 N\t14,25 E\nb\t1\n", &opts);
+    assert_eq!("a\t40.5\t14.25\nb\t1\n", out);
+    assert_eq!("row 2: Missing column 3\n", errors);
+    assert!(!ok);
+}
+
+#[test]
+fn output_styles() {
+    let p = Point::new(-79.982, 40.446);
+    let format = |style, precision| format_point(p, style, precision).unwrap();
+
+    assert_eq!("40.446,-79.982", format(Style::Decimal, None));
+    assert_eq!("40° 26.7600′ N, 79° 58.9200′ W", format(Style::Dm, None));
+    assert_eq!("40° 27′ N, 79° 59′ W", format(Style::Dm, Some(0)));
+    assert_eq!("40° 26′ 45.6″ N, 79° 58′ 55.2″ W", format(Style::Dms, Some(1)));
+    assert_eq!("17T 586329 4477758", format(Style::Utm, None));
+    assert_eq!("17TNE8632877758", format(Style::Mgrs, None));
+    assert_eq!("dppn5", format(Style::Geohash, Some(5)));
+    assert_eq!("POINT(-79.982 40.446)", format(Style::Wkt, None));
+    assert_eq!(r#"{"type":"Point","coordinates":[-79.98,40.45]}"#, format(Style::GeoJson, Some(2)));
+
+    assert_eq!("Z 2000000 1444543", format_point(Point::new(0., 85.), Style::Utm, None).unwrap());
+    assert!(format_point(Point::new(0., 85.), Style::Mgrs, None).is_err());
+}
+
+#[test]
+fn precision_limit() {
+    let args = |p: &str| parse_args(["-f", "dm", "-p", p, "40.446 -79.982"].iter().map(|s| s.to_string()));
+
+    assert_eq!(Some(15), args("15").unwrap().precision);
+    assert_eq!("Invalid precision: 16", args("16").unwrap_err());
+    assert!(args("400").is_err());
+    assert!(args("4294967296").is_err());
+    assert!(args("-1").is_err());
+}
@@ -0,0 +1,287 @@
+//! Geodetic datums, their ellipsoids and 7-parameter Helmert transformations to WGS84.
+//!
+//! Transformations go through geocentric cartesian coordinates. The parameters are regional
+//! averages, accurate to a few meters, which is as good as a Helmert transform of these datums gets.
+
+use crate::Point;
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Reference ellipsoid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ellipsoid {
+    /// Semi-major axis (m)
+    pub a: f64,
+    /// Flattening
+    pub f: f64,
+}
+
+impl Ellipsoid {
+    /// WGS84
+    pub const WGS84: Ellipsoid = Ellipsoid {
+        a: 6378137.,
+        f: 1. / 298.257223563,
+    };
+    /// GRS 1980 (ETRS89, NAD83)
+    pub const GRS80: Ellipsoid = Ellipsoid {
+        a: 6378137.,
+        f: 1. / 298.257222101,
+    };
+    /// Krassovsky 1940 (S-42)
+    pub const KRASSOVSKY: Ellipsoid = Ellipsoid {
+        a: 6378245.,
+        f: 1. / 298.3,
+    };
+    /// International 1924, also called Hayford (ED50)
+    pub const INTERNATIONAL: Ellipsoid = Ellipsoid {
+        a: 6378388.,
+        f: 1. / 297.,
+    };
+    /// Clarke 1866 (NAD27)
+    pub const CLARKE_1866: Ellipsoid = Ellipsoid {
+        a: 6378206.4,
+        f: 1. / 294.978698214,
+    };
+    /// Bessel 1841 (S-JTSK, DHDN)
+    pub const BESSEL: Ellipsoid = Ellipsoid {
+        a: 6377397.155,
+        f: 1. / 299.1528128,
+    };
+    /// Airy 1830 (OSGB36)
+    pub const AIRY: Ellipsoid = Ellipsoid {
+        a: 6377563.396,
+        f: 1. / 299.3249646,
+    };
+    /// Modified Airy (Ireland 1965)
+    pub const AIRY_MODIFIED: Ellipsoid = Ellipsoid {
+        a: 6377340.189,
+        f: 1. / 299.3249646,
+    };
+
+    /// Square of the first eccentricity
+    pub fn e2(&self) -> f64 {
+        self.f * (2. - self.f)
+    }
+
+    /// Semi-minor axis (m)
+    pub fn b(&self) -> f64 {
+        self.a * (1. - self.f)
+    }
+
+    /// Convert geodetic latitude, longitude (degrees) and height (m) to geocentric cartesian coordinates (m)
+    pub fn cartesian(&self, lat: f64, lng: f64, h: f64) -> [f64; 3] {
+        let (phi, lambda) = (lat.to_radians(), lng.to_radians());
+        let e2 = self.e2();
+        let n = self.a / (1. - e2 * phi.sin() * phi.sin()).sqrt();
+
+        [
+            (n + h) * phi.cos() * lambda.cos(),
+            (n + h) * phi.cos() * lambda.sin(),
+            (n * (1. - e2) + h) * phi.sin(),
+        ]
+    }
+
+    /// Convert geocentric cartesian coordinates (m) to geodetic latitude, longitude (degrees)
+    /// and height (m), using Bowring's formula
+    pub fn geodetic(&self, xyz: [f64; 3]) -> (f64, f64, f64) {
+        let [x, y, z] = xyz;
+        let (a, b, e2) = (self.a, self.b(), self.e2());
+        let ep2 = e2 / (1. - e2);
+
+        let p = (x * x + y * y).sqrt();
+        let theta = (z * a).atan2(p * b);
+        let (sin_t, cos_t) = (theta.sin(), theta.cos());
+        let phi = (z + ep2 * b * sin_t * sin_t * sin_t).atan2(p - e2 * a * cos_t * cos_t * cos_t);
+        let lambda = y.atan2(x);
+
+        let n = a / (1. - e2 * phi.sin() * phi.sin()).sqrt();
+        let h = if phi.cos().abs() > 1e-9 {
+            p / phi.cos() - n
+        } else {
+            z.abs() - b
+        };
+
+        (phi.to_degrees(), lambda.to_degrees(), h)
+    }
+}
+
+/// 7-parameter Helmert transformation, in the position vector convention (EPSG method 9606).
+///
+/// Parameters of the coordinate frame convention (EPSG 9607) are converted by negating the rotations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Helmert {
+    /// Translation (m)
+    pub tx: f64,
+    pub ty: f64,
+    pub tz: f64,
+    /// Rotation (arc seconds)
+    pub rx: f64,
+    pub ry: f64,
+    pub rz: f64,
+    /// Scale difference (ppm)
+    pub s: f64,
+}
+
+impl Helmert {
+    /// Identity transformation
+    pub const IDENTITY: Helmert = Helmert::translation(0., 0., 0.);
+
+    /// Transformation with translation only
+    pub const fn translation(tx: f64, ty: f64, tz: f64) -> Helmert {
+        Helmert {
+            tx,
+            ty,
+            tz,
+            rx: 0.,
+            ry: 0.,
+            rz: 0.,
+            s: 0.,
+        }
+    }
+
+    /// Get the reverse transformation (approximate, to about a millimeter for the usual parameters)
+    pub fn inverse(&self) -> Helmert {
+        Helmert {
+            tx: -self.tx,
+            ty: -self.ty,
+            tz: -self.tz,
+            rx: -self.rx,
+            ry: -self.ry,
+            rz: -self.rz,
+            s: -self.s,
+        }
+    }
+
+    /// Transform geocentric cartesian coordinates (m)
+    pub fn apply(&self, xyz: [f64; 3]) -> [f64; 3] {
+        let [x, y, z] = xyz;
+        let arcsec = (1f64 / 3600.).to_radians();
+        let (rx, ry, rz) = (self.rx * arcsec, self.ry * arcsec, self.rz * arcsec);
+        let m = 1. + self.s * 1e-6;
+
+        [
+            self.tx + m * (x - rz * y + ry * z),
+            self.ty + m * (rz * x + y - rx * z),
+            self.tz + m * (-ry * x + rx * y + z),
+        ]
+    }
+}
+
+/// Geodetic datum of coordinates.
+///
+/// Points returned by the parsing functions are WGS84, unless `ParseOptions::datum` says otherwise.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[non_exhaustive]
+pub enum Datum {
+    /// World Geodetic System 1984, used by GPS
+    #[default]
+    Wgs84,
+    /// European Terrestrial Reference System 1989, taken as equal to WGS84 (within a meter): points are
+    /// not transformed between the two. Projections use its GRS80 ellipsoid.
+    Etrs89,
+    /// S-42 (Pulkovo 1942), with the parameters of GOST R 51794-2008
+    S42,
+    /// European Datum 1950, western Europe average (EPSG:1133)
+    Ed50,
+    /// North American Datum 1927, contiguous United States average (EPSG:1173)
+    Nad27,
+    /// S-JTSK, the Czech and Slovak cadastral datum (EPSG:1623)
+    Sjtsk,
+    /// OSGB36, the datum of the British National Grid (EPSG:1314)
+    Osgb36,
+    /// TM65 (Ireland 1965), the datum of the Irish Grid (EPSG:1641)
+    Tm65,
+    /// DHDN (Potsdam), the German datum of Gauss-Krüger coordinates, Germany average (EPSG:1777)
+    Dhdn,
+}
+
+impl Datum {
+    /// Get the reference ellipsoid
+    pub fn ellipsoid(self) -> Ellipsoid {
+        match self {
+            Datum::Wgs84 => Ellipsoid::WGS84,
+            Datum::Etrs89 => Ellipsoid::GRS80,
+            Datum::S42 => Ellipsoid::KRASSOVSKY,
+            Datum::Ed50 => Ellipsoid::INTERNATIONAL,
+            Datum::Nad27 => Ellipsoid::CLARKE_1866,
+            Datum::Sjtsk | Datum::Dhdn => Ellipsoid::BESSEL,
+            Datum::Osgb36 => Ellipsoid::AIRY,
+            Datum::Tm65 => Ellipsoid::AIRY_MODIFIED,
+        }
+    }
+
+    /// Get the transformation of geocentric coordinates to WGS84
+    pub fn helmert(self) -> Helmert {
+        match self {
+            Datum::Wgs84 | Datum::Etrs89 => Helmert::IDENTITY,
+            Datum::S42 => Helmert {
+                tx: 23.57,
+                ty: -140.95,
+                tz: -79.8,
+                rx: 0.,
+                ry: 0.35,
+                rz: 0.79,
+                s: -0.22,
+            },
+            Datum::Ed50 => Helmert::translation(-87., -98., -121.),
+            Datum::Nad27 => Helmert::translation(-8., 160., 176.),
+            Datum::Sjtsk => Helmert {
+                tx: 570.8,
+                ty: 85.7,
+                tz: 462.8,
+                rx: 4.998,
+                ry: 1.587,
+                rz: 5.261,
+                s: 3.56,
+            },
+            Datum::Osgb36 => Helmert {
+                tx: 446.448,
+                ty: -125.157,
+                tz: 542.06,
+                rx: 0.15,
+                ry: 0.247,
+                rz: 0.842,
+                s: -20.489,
+            },
+            Datum::Tm65 => Helmert {
+                tx: 482.5,
+                ty: -130.6,
+                tz: 564.6,
+                rx: -1.042,
+                ry: -0.214,
+                rz: -0.631,
+                s: 8.15,
+            },
+            Datum::Dhdn => Helmert {
+                tx: 598.1,
+                ty: 73.7,
+                tz: 418.2,
+                rx: 0.202,
+                ry: 0.045,
+                rz: -2.455,
+                s: 6.7,
+            },
+        }
+    }
+
+    /// Convert a point in this datum to WGS84
+    pub fn to_wgs84(self, point: Point<f64>) -> Point<f64> {
+        self.transform(point, Datum::Wgs84)
+    }
+
+    /// Convert a point in this datum to another datum
+    pub fn transform(self, point: Point<f64>, to: Datum) -> Point<f64> {
+        let same_as_wgs84 = |datum| matches!(datum, Datum::Wgs84 | Datum::Etrs89);
+        if (self.ellipsoid() == to.ellipsoid() && self.helmert() == to.helmert())
+            || (same_as_wgs84(self) && same_as_wgs84(to))
+        {
+            return point;
+        }
+
+        let xyz = self.ellipsoid().cartesian(point.y(), point.x(), 0.);
+        let xyz = to.helmert().inverse().apply(self.helmert().apply(xyz));
+        let (lat, lng, _) = to.ellipsoid().geodetic(xyz);
+
+        Point::new(lng, lat)
+    }
+}
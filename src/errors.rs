@@ -1,21 +1,6 @@
-use std::fmt;
-use std::fmt::{Display, Formatter, Debug};
-use std::num::ParseFloatError;
-
-#[derive(Debug)]
-pub(crate) struct ParseErrorInternal;
-
-impl From<ParseFloatError> for ParseErrorInternal {
-    fn from(_: ParseFloatError) -> Self {
-        ParseErrorInternal
-    }
-}
-
-impl Display for ParseErrorInternal {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.write_str("Parse error")
-    }
-}
+use core::fmt;
+use core::fmt::{Display, Formatter, Debug};
+use core::ops::Range;
 
 pub struct GeoParseError<T: AsRef<str>>(pub T);
 
@@ -33,4 +18,143 @@ impl<T : AsRef<str>> Debug for GeoParseError<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl GeoParseError<&str> {
+    /// Copy the source string, so the error can outlive it
+    pub fn into_owned(self) -> GeoParseError<alloc::string::String> {
+        GeoParseError(self.0.into())
+    }
+}
+
+#[cfg(feature = "std")]
 impl<T : AsRef<str>> std::error::Error for GeoParseError<T> {}
+
+/// What went wrong when parsing, see `ParseError`
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The input contains no coordinate
+    Empty,
+    /// A character that is not part of any supported format
+    UnexpectedChar,
+    /// An unknown unit suffix (lowercase word)
+    UnknownUnit,
+    /// A malformed number, e.g. a sign without digits
+    InvalidNumber,
+    /// The input has more parts than any supported format
+    TooLong,
+    /// The parts do not form any supported format
+    Format,
+    /// Hemisphere letter of the other axis, e.g. `E` in latitude
+    Hemisphere,
+    /// Sign conflicting with the hemisphere letter, see `SignPolicy`
+    Sign,
+    /// Coordinate out of range, see `LatRangePolicy` and `LngRangePolicy`
+    OutOfRange,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseErrorKind::Empty => "No coordinates",
+            ParseErrorKind::UnexpectedChar => "Unexpected character",
+            ParseErrorKind::UnknownUnit => "Unknown unit",
+            ParseErrorKind::InvalidNumber => "Invalid number",
+            ParseErrorKind::TooLong => "Too many parts",
+            ParseErrorKind::Format => "Unrecognized format",
+            ParseErrorKind::Hemisphere => "Wrong hemisphere",
+            ParseErrorKind::Sign => "Sign conflicts with hemisphere",
+            ParseErrorKind::OutOfRange => "Coordinate out of range",
+        })
+    }
+}
+
+/// Parse error with its kind and location, returned by the non-allocating `parse_str()`,
+/// `parse_lat_str()` and `parse_lng_str()`.
+///
+/// The span is a byte range in the parsed string.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    start: usize,
+    end: usize,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, start: usize, end: usize) -> Self {
+        ParseError { kind, start, end }
+    }
+
+    /// Move the span by the offset of the parsed part in a longer string
+    #[cfg(feature = "alloc")]
+    pub(crate) fn shifted(self, offset: usize) -> Self {
+        ParseError::new(self.kind, self.start + offset, self.end + offset)
+    }
+
+    /// Get the kind of error
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Get the byte range of the offending part of the input
+    pub fn span(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.kind, self.start, self.end)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Error parsing a list of coordinates, returned by `parse_many()`: which item failed, and why.
+///
+/// The span of the inner error is a byte range in the whole parsed string.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ItemParseError {
+    index: usize,
+    error: ParseError,
+}
+
+impl ItemParseError {
+    #[cfg(feature = "alloc")]
+    pub(crate) fn new(index: usize, error: ParseError) -> Self {
+        ItemParseError { index, error }
+    }
+
+    /// Get the index of the item that failed (from 0)
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the error of the item
+    pub fn error(&self) -> ParseError {
+        self.error
+    }
+}
+
+impl Display for ItemParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Item {}: {}", self.index, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ItemParseError {}
+
+/// Coordinate is out of the allowed range, or has a wrong hemisphere
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct OutOfRange;
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Coordinate out of range")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OutOfRange {}
@@ -0,0 +1,169 @@
+use crate::GeoParseError;
+use crate::Point;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Scale of a fixed-point coordinate, i.e. the size of one integer unit
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum FixedScale {
+    /// 1e-6 degree (microdegree)
+    E6,
+    /// 1e-7 degree, used e.g. by OSM PBF and MAVLink
+    E7,
+}
+
+impl FixedScale {
+    /// Number of decimal places
+    pub fn decimals(self) -> usize {
+        match self {
+            FixedScale::E6 => 6,
+            FixedScale::E7 => 7,
+        }
+    }
+
+    /// Number of units in one degree
+    pub fn units_per_degree(self) -> i32 {
+        match self {
+            FixedScale::E6 => 1_000_000,
+            FixedScale::E7 => 10_000_000,
+        }
+    }
+}
+
+/// Coordinate stored as integer latitude and longitude in fixed-point units.
+///
+/// Conversion to `Point<f64>` is exact to the nearest `f64`, and converting that
+/// back with the same scale always gives the original integers.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct FixedPoint {
+    lat: i32,
+    lng: i32,
+    scale: FixedScale,
+}
+
+impl FixedPoint {
+    /// Create from integer latitude and longitude in the given scale.
+    ///
+    /// Returns `None` if the coordinates are out of range (±90° latitude, ±180° longitude).
+    pub fn new(lat: i32, lng: i32, scale: FixedScale) -> Option<FixedPoint> {
+        let unit = scale.units_per_degree();
+
+        if lat.checked_abs()? > 90 * unit || lng.checked_abs()? > 180 * unit {
+            return None;
+        }
+
+        Some(FixedPoint { lat, lng, scale })
+    }
+
+    /// Convert a point, rounding to the nearest unit of the given scale.
+    ///
+    /// Returns `None` if the coordinates are out of range or not finite.
+    pub fn from_point(point: Point<f64>, scale: FixedScale) -> Option<FixedPoint> {
+        let unit = f64::from(scale.units_per_degree());
+        let lat = (point.y() * unit).round();
+        let lng = (point.x() * unit).round();
+
+        if !(lat.is_finite() && lng.is_finite()) || lat.abs() > 90f64 * unit || lng.abs() > 180f64 * unit {
+            return None;
+        }
+
+        FixedPoint::new(lat as i32, lng as i32, scale)
+    }
+
+    /// Parse a pair of integers (latitude, longitude) in the given scale,
+    /// e.g. `404460000, -799820000` with `FixedScale::E7`.
+    ///
+    /// The numbers can be separated by comma (`,`), semicolon (`;`) or whitespace.
+    pub fn parse<T: AsRef<str> + Display>(text: T, scale: FixedScale) -> Result<FixedPoint, GeoParseError<T>> {
+        match do_parse_fixed(text.as_ref().trim(), scale) {
+            Some(p) => Ok(p),
+            None => Err(GeoParseError(text)),
+        }
+    }
+
+    /// Latitude in units of the scale
+    pub fn lat(&self) -> i32 {
+        self.lat
+    }
+
+    /// Longitude in units of the scale
+    pub fn lng(&self) -> i32 {
+        self.lng
+    }
+
+    /// The scale
+    pub fn scale(&self) -> FixedScale {
+        self.scale
+    }
+
+    /// Convert to a different scale, rounding to the nearest unit (half away from zero)
+    pub fn rescale(&self, scale: FixedScale) -> FixedPoint {
+        let from = self.scale.units_per_degree();
+        let to = scale.units_per_degree();
+
+        let convert = |v: i32| -> i32 {
+            if to >= from {
+                v * (to / from)
+            } else {
+                let div = from / to;
+                let half = div / 2;
+                if v >= 0 {
+                    (v + half) / div
+                } else {
+                    (v - half) / div
+                }
+            }
+        };
+
+        FixedPoint {
+            lat: convert(self.lat),
+            lng: convert(self.lng),
+            scale,
+        }
+    }
+}
+
+/// Parse a pair of integers
+fn do_parse_fixed(s: &str, scale: FixedScale) -> Option<FixedPoint> {
+    let (lat, lng) = match s.find([',', ';']) {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => {
+            let i = s.find(char::is_whitespace)?;
+            (&s[..i], &s[i..])
+        }
+    };
+
+    FixedPoint::new(lat.trim().parse().ok()?, lng.trim().parse().ok()?, scale)
+}
+
+impl From<FixedPoint> for Point<f64> {
+    fn from(p: FixedPoint) -> Self {
+        let unit = f64::from(p.scale.units_per_degree());
+        Point::new(f64::from(p.lng) / unit, f64::from(p.lat) / unit)
+    }
+}
+
+/// Write a fixed-point value as exact decimal degrees
+fn write_decimal(f: &mut Formatter<'_>, value: i32, scale: FixedScale) -> fmt::Result {
+    let unit = scale.units_per_degree().unsigned_abs();
+    let abs = value.unsigned_abs();
+    let sign = if value < 0 { "-" } else { "" };
+    write!(f, "{}{}.{:0width$}", sign, abs / unit, abs % unit, width = scale.decimals())
+}
+
+/// Formats as the integer pair `lat,lng` (as accepted by `FixedPoint::parse()`).
+///
+/// The alternate form (`{:#}`) writes exact decimal degrees instead, e.g. `40.4460000,-79.9820000`.
+impl Display for FixedPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write_decimal(f, self.lat, self.scale)?;
+            f.write_str(",")?;
+            write_decimal(f, self.lng, self.scale)
+        } else {
+            write!(f, "{},{}", self.lat, self.lng)
+        }
+    }
+}
@@ -0,0 +1,953 @@
+//! Geodesics on the ellipsoid (Karney's algorithm, accurate to a few nanometers and converging
+//! for all pairs of points, including nearly antipodal ones where Vincenty's method fails),
+//! and great circles on a sphere.
+//!
+//! C. F. F. Karney, Algorithms for geodesics, J. Geodesy 87, 43–55 (2013), following the series
+//! to the 6th order of GeographicLib.
+
+use crate::datum::Ellipsoid;
+use crate::Point;
+use core::f64::consts::PI;
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Mean Earth radius (m) used by the spherical functions, the IUGG mean radius of WGS84
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Order of the series
+const N: usize = 6;
+/// Number of coefficients of the `C3` series
+const N_C3X: usize = N * (N - 1) / 2;
+
+const MAXIT1: usize = 20;
+const MAXIT2: usize = MAXIT1 + f64::MANTISSA_DIGITS as usize + 10;
+
+const TOL0: f64 = f64::EPSILON;
+const TOL1: f64 = 200. * TOL0;
+
+/// Smallest number whose square is normal
+fn tiny() -> f64 {
+    f64::MIN_POSITIVE.sqrt()
+}
+
+fn tol2() -> f64 {
+    TOL0.sqrt()
+}
+
+/// Distance and azimuths of the shortest path between two points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeodesicInverse {
+    /// Distance (m)
+    pub distance: f64,
+    /// Azimuth at the first point (degrees clockwise from north, -180 to 180)
+    pub initial_azimuth: f64,
+    /// Azimuth at the second point, in the direction of travel (degrees, -180 to 180)
+    pub final_azimuth: f64,
+}
+
+/// End of a path from a point at an azimuth
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeodesicDirect {
+    /// Destination
+    pub point: Point<f64>,
+    /// Azimuth at the destination, in the direction of travel (degrees, -180 to 180)
+    pub final_azimuth: f64,
+}
+
+/// Geodesic calculations on an ellipsoid
+#[derive(Debug, Clone, Copy)]
+pub struct Geodesic {
+    a: f64,
+    f: f64,
+    f1: f64,
+    ep2: f64,
+    n: f64,
+    b: f64,
+    etol2: f64,
+    a3x: [f64; N],
+    c3x: [f64; N_C3X],
+}
+
+impl Geodesic {
+    /// Geodesics on the ellipsoid
+    pub fn new(ellipsoid: Ellipsoid) -> Geodesic {
+        let (a, f) = (ellipsoid.a, ellipsoid.f);
+        let f1 = 1. - f;
+        let e2 = f * (2. - f);
+        let n = f / (2. - f);
+
+        Geodesic {
+            a,
+            f,
+            f1,
+            ep2: e2 / (f1 * f1),
+            n,
+            b: a * f1,
+            etol2: 0.1 * tol2() / (f.abs().max(0.001) * (1. - f / 2.).min(1.) / 2.).sqrt(),
+            a3x: a3_coeff(n),
+            c3x: c3_coeff(n),
+        }
+    }
+
+    /// Geodesics on the WGS84 ellipsoid
+    pub fn wgs84() -> Geodesic {
+        Geodesic::new(Ellipsoid::WGS84)
+    }
+
+    /// Solve the inverse geodesic problem: the shortest distance between two points,
+    /// and the azimuths at both ends.
+    ///
+    /// The result is NaN if a latitude is out of range.
+    pub fn inverse(&self, p1: Point<f64>, p2: Point<f64>) -> GeodesicInverse {
+        let (s12, salp1, calp1, salp2, calp2) = self.gen_inverse(p1.y(), p1.x(), p2.y(), p2.x());
+        GeodesicInverse {
+            distance: s12,
+            initial_azimuth: atan2d(salp1, calp1),
+            final_azimuth: atan2d(salp2, calp2),
+        }
+    }
+
+    /// Get the shortest distance between two points (m)
+    pub fn distance(&self, p1: Point<f64>, p2: Point<f64>) -> f64 {
+        self.inverse(p1, p2).distance
+    }
+
+    /// Solve the direct geodesic problem: the destination at a distance (m, may be negative)
+    /// from a point at an azimuth (degrees clockwise from north).
+    ///
+    /// The result is NaN if the latitude is out of range.
+    pub fn direct(&self, point: Point<f64>, azimuth: f64, distance: f64) -> GeodesicDirect {
+        let (lat1, lon1) = (lat_fix(point.y()), point.x());
+        let (salp1, calp1) = sincosd(ang_round(azimuth));
+        let (sbet1, cbet1) = reduced_latitude(ang_round(lat1), self.f1);
+
+        // the great circle on the auxiliary sphere
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+        let somg1 = salp0 * sbet1;
+        let comg1 = if sbet1 != 0. || calp1 != 0. { cbet1 * calp1 } else { 1. };
+        let (ssig1, csig1) = norm(sbet1, comg1);
+
+        let k2 = sq(calp0) * self.ep2;
+        let eps = k2 / (2. * (1. + (1. + k2).sqrt()) + k2);
+
+        let a1m1 = a1m1f(eps);
+        let mut c1a = [0.; N + 1];
+        c1f(eps, &mut c1a);
+        let b11 = sin_cos_series(true, ssig1, csig1, &c1a);
+        let (stau1, ctau1) = {
+            let (s, c) = (b11.sin(), b11.cos());
+            (ssig1 * c + csig1 * s, csig1 * c - ssig1 * s)
+        };
+        let mut c1pa = [0.; N + 1];
+        c1pf(eps, &mut c1pa);
+
+        let a3c = -self.f * salp0 * self.a3f(eps);
+        let mut c3a = [0.; N];
+        self.c3f(eps, &mut c3a);
+        let b31 = sin_cos_series(true, ssig1, csig1, &c3a);
+
+        // arc length on the auxiliary sphere of the distance
+        let tau12 = distance / (self.b * (1. + a1m1));
+        let (s, c) = (tau12.sin(), tau12.cos());
+        let b12 = -sin_cos_series(true, stau1 * c + ctau1 * s, ctau1 * c - stau1 * s, &c1pa);
+        let sig12 = tau12 - (b12 - b11);
+        let (ssig12, csig12) = (sig12.sin(), sig12.cos());
+
+        let ssig2 = ssig1 * csig12 + csig1 * ssig12;
+        let mut csig2 = csig1 * csig12 - ssig1 * ssig12;
+        let sbet2 = calp0 * ssig2;
+        let mut cbet2 = salp0.hypot(calp0 * csig2);
+        if cbet2 == 0. {
+            // at a pole
+            cbet2 = tiny();
+            csig2 = tiny();
+        }
+        let salp2 = salp0;
+        let calp2 = calp0 * csig2;
+
+        let somg2 = salp0 * ssig2;
+        let comg2 = csig2;
+        let omg12 = (somg2 * comg1 - comg2 * somg1).atan2(comg2 * comg1 + somg2 * somg1);
+        let lam12 = omg12 + a3c * (sig12 + (sin_cos_series(true, ssig2, csig2, &c3a) - b31));
+        let lon2 = ang_normalize(ang_normalize(lon1) + ang_normalize(lam12.to_degrees()));
+        let lat2 = atan2d(sbet2, self.f1 * cbet2);
+
+        GeodesicDirect {
+            point: Point::new(lon2, lat2),
+            final_azimuth: atan2d(salp2, calp2),
+        }
+    }
+
+    /// Get the destination at a distance (m) from a point at an azimuth (degrees), see `direct()`
+    pub fn destination(&self, point: Point<f64>, azimuth: f64, distance: f64) -> Point<f64> {
+        self.direct(point, azimuth, distance).point
+    }
+
+    /// Get the point at a fraction of the shortest path between two points
+    /// (0 is the first point, 1 the second, values outside extend the path)
+    pub fn intermediate(&self, p1: Point<f64>, p2: Point<f64>, fraction: f64) -> Point<f64> {
+        let path = self.inverse(p1, p2);
+        self.destination(p1, path.initial_azimuth, path.distance * fraction)
+    }
+
+    /// Get the point halfway along the shortest path between two points
+    pub fn midpoint(&self, p1: Point<f64>, p2: Point<f64>) -> Point<f64> {
+        self.intermediate(p1, p2, 0.5)
+    }
+
+    fn a3f(&self, eps: f64) -> f64 {
+        polyval(&self.a3x, eps)
+    }
+
+    /// Coefficients of the `C3` series, `c[1..N]`
+    fn c3f(&self, eps: f64, c: &mut [f64; N]) {
+        let mut mult = 1.;
+        let mut o = 0;
+        for (l, c) in c.iter_mut().enumerate().skip(1) {
+            let m = N - l - 1;
+            mult *= eps;
+            *c = mult * polyval(&self.c3x[o..=o + m], eps);
+            o += m + 1;
+        }
+    }
+
+    /// Scaled distance and reduced length, returns `(s12 / b, m12 / b, m0)`
+    #[allow(clippy::too_many_arguments)]
+    fn lengths(
+        &self,
+        eps: f64,
+        sig12: f64,
+        (ssig1, csig1, dn1): (f64, f64, f64),
+        (ssig2, csig2, dn2): (f64, f64, f64),
+        c1a: &mut [f64; N + 1],
+        c2a: &mut [f64; N + 1],
+    ) -> (f64, f64, f64) {
+        let a1 = a1m1f(eps);
+        c1f(eps, c1a);
+        let a2 = a2m1f(eps);
+        c2f(eps, c2a);
+        let m0x = a1 - a2;
+        let (a1, a2) = (1. + a1, 1. + a2);
+
+        let b1 = sin_cos_series(true, ssig2, csig2, c1a) - sin_cos_series(true, ssig1, csig1, c1a);
+        let s12b = a1 * (sig12 + b1);
+        let b2 = sin_cos_series(true, ssig2, csig2, c2a) - sin_cos_series(true, ssig1, csig1, c2a);
+        let j12 = m0x * sig12 + (a1 * b1 - a2 * b2);
+        let m12b = dn2 * (csig1 * ssig2) - dn1 * (ssig1 * csig2) - csig1 * csig2 * j12;
+
+        (s12b, m12b, m0x)
+    }
+
+    /// Starting azimuth of the Newton iteration, returns `(sig12, salp1, calp1, salp2, calp2, dnm)`,
+    /// where `sig12` is non-negative if the problem was solved for short lines
+    #[allow(clippy::too_many_arguments)]
+    fn inverse_start(
+        &self,
+        (sbet1, cbet1): (f64, f64),
+        (sbet2, cbet2): (f64, f64),
+        lam12: f64,
+        slam12: f64,
+        clam12: f64,
+    ) -> (f64, f64, f64, f64, f64, f64) {
+        let mut sig12 = -1.;
+        let (mut salp2, mut calp2, mut dnm) = (f64::NAN, f64::NAN, f64::NAN);
+
+        let sbet12 = sbet2 * cbet1 - cbet2 * sbet1;
+        let cbet12 = cbet2 * cbet1 + sbet2 * sbet1;
+        let sbet12a = sbet2 * cbet1 + cbet2 * sbet1;
+
+        let shortline = cbet12 >= 0. && sbet12 < 0.5 && cbet2 * lam12 < 0.5;
+        let (somg12, comg12) = if shortline {
+            let mut sbetm2 = sq(sbet1 + sbet2);
+            sbetm2 /= sbetm2 + sq(cbet1 + cbet2);
+            dnm = (1. + self.ep2 * sbetm2).sqrt();
+            let omg12 = lam12 / (self.f1 * dnm);
+            (omg12.sin(), omg12.cos())
+        } else {
+            (slam12, clam12)
+        };
+
+        let mut salp1 = cbet2 * somg12;
+        let mut calp1 = if comg12 >= 0. {
+            sbet12 + cbet2 * sbet1 * sq(somg12) / (1. + comg12)
+        } else {
+            sbet12a - cbet2 * sbet1 * sq(somg12) / (1. - comg12)
+        };
+
+        let ssig12 = salp1.hypot(calp1);
+        let csig12 = sbet1 * sbet2 + cbet1 * cbet2 * comg12;
+
+        if shortline && ssig12 < self.etol2 {
+            // really short lines
+            salp2 = cbet1 * somg12;
+            calp2 = sbet12
+                - cbet1
+                    * sbet2
+                    * if comg12 >= 0. {
+                        sq(somg12) / (1. + comg12)
+                    } else {
+                        1. - comg12
+                    };
+            let (s, c) = norm(salp2, calp2);
+            salp2 = s;
+            calp2 = c;
+            sig12 = ssig12.atan2(csig12);
+        } else if self.n.abs() >= 0.1 || csig12 >= 0. || ssig12 >= 6. * self.n.abs() * PI * sq(cbet1) {
+            // nothing to do, zeroth order spherical approximation is OK
+        } else {
+            // nearly antipodal points, scale to an astroid problem
+            let lam12x = (-slam12).atan2(-clam12);
+            let k2 = sq(sbet1) * self.ep2;
+            let eps = k2 / (2. * (1. + (1. + k2).sqrt()) + k2);
+            let lamscale = self.f * cbet1 * self.a3f(eps) * PI;
+            let betscale = lamscale * cbet1;
+            let x = lam12x / lamscale;
+            let y = sbet12a / betscale;
+
+            if y > -TOL1 && x > -1. - 1000. * tol2() {
+                salp1 = (-x).min(1.);
+                calp1 = -(1. - sq(salp1)).sqrt();
+            } else {
+                let k = astroid(x, y);
+                let omg12a = lamscale * (-x * k / (1. + k));
+                let (somg12, comg12) = (omg12a.sin(), -omg12a.cos());
+                salp1 = cbet2 * somg12;
+                calp1 = sbet12a - cbet2 * sbet1 * sq(somg12) / (1. - comg12);
+            }
+        }
+
+        let (salp1, calp1) = if salp1 > 0. { norm(salp1, calp1) } else { (1., 0.) };
+        (sig12, salp1, calp1, salp2, calp2, dnm)
+    }
+
+    /// Longitude difference of the geodesic with the starting azimuth, and its derivative
+    /// (if `diffp`), returns `(lam12, salp2, calp2, sig12, ssig1, csig1, ssig2, csig2, eps, dlam12)`
+    #[allow(clippy::too_many_arguments)]
+    fn lambda12(
+        &self,
+        (sbet1, cbet1, dn1): (f64, f64, f64),
+        (sbet2, cbet2, dn2): (f64, f64, f64),
+        salp1: f64,
+        calp1: f64,
+        slam120: f64,
+        clam120: f64,
+        diffp: bool,
+        c1a: &mut [f64; N + 1],
+        c2a: &mut [f64; N + 1],
+        c3a: &mut [f64; N],
+    ) -> Lambda12 {
+        let calp1 = if sbet1 == 0. && calp1 == 0. { -tiny() } else { calp1 };
+
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+
+        let somg1 = salp0 * sbet1;
+        let comg1 = calp1 * cbet1;
+        let (ssig1, csig1) = norm(sbet1, comg1);
+
+        let salp2 = if cbet2 != cbet1 { salp0 / cbet2 } else { salp1 };
+        let calp2 = if cbet2 != cbet1 || sbet2.abs() != -sbet1 {
+            let d = if cbet1 < -sbet1 {
+                (cbet2 - cbet1) * (cbet1 + cbet2)
+            } else {
+                (sbet1 - sbet2) * (sbet1 + sbet2)
+            };
+            (sq(calp1 * cbet1) + d).sqrt() / cbet2
+        } else {
+            calp1.abs()
+        };
+
+        let somg2 = salp0 * sbet2;
+        let comg2 = calp2 * cbet2;
+        let (ssig2, csig2) = norm(sbet2, comg2);
+
+        let sig12 = ((csig1 * ssig2 - ssig1 * csig2).max(0.) + 0.).atan2(csig1 * csig2 + ssig1 * ssig2);
+        let somg12 = (comg1 * somg2 - somg1 * comg2).max(0.) + 0.;
+        let comg12 = comg1 * comg2 + somg1 * somg2;
+        let eta = (somg12 * clam120 - comg12 * slam120).atan2(comg12 * clam120 + somg12 * slam120);
+
+        let k2 = sq(calp0) * self.ep2;
+        let eps = k2 / (2. * (1. + (1. + k2).sqrt()) + k2);
+        self.c3f(eps, c3a);
+        let b312 = sin_cos_series(true, ssig2, csig2, c3a) - sin_cos_series(true, ssig1, csig1, c3a);
+        let domg12 = -self.f * self.a3f(eps) * salp0 * (sig12 + b312);
+        let lam12 = eta + domg12;
+
+        let dlam12 = if !diffp {
+            f64::NAN
+        } else if calp2 == 0. {
+            -2. * self.f1 * dn1 / sbet1
+        } else {
+            let (_, m12b, _) = self.lengths(eps, sig12, (ssig1, csig1, dn1), (ssig2, csig2, dn2), c1a, c2a);
+            m12b * self.f1 / (calp2 * cbet2)
+        };
+
+        Lambda12 {
+            lam12,
+            salp2,
+            calp2,
+            sig12,
+            sig1: (ssig1, csig1),
+            sig2: (ssig2, csig2),
+            eps,
+            dlam12,
+        }
+    }
+
+    /// Solve the inverse problem, returns `(s12, salp1, calp1, salp2, calp2)`
+    fn gen_inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64, f64, f64, f64) {
+        // make longitude difference positive, and take the first point to have the larger |latitude|,
+        // which is then made negative
+        let (lon12, lon12s) = ang_diff(lon1, lon2);
+        let mut lonsign = if lon12.is_sign_negative() { -1. } else { 1. };
+        let lon12 = lonsign * ang_round(lon12);
+        let lon12s = ang_round((180. - lon12) - lonsign * lon12s);
+        let lam12 = lon12.to_radians();
+        let (slam12, clam12) = if lon12 > 90. {
+            let (s, c) = sincosd(lon12s);
+            (s, -c)
+        } else {
+            sincosd(lon12)
+        };
+
+        let mut lat1 = ang_round(lat_fix(lat1));
+        let mut lat2 = ang_round(lat_fix(lat2));
+        let swapp = if lat1.abs() < lat2.abs() || lat2.is_nan() { -1. } else { 1. };
+        if swapp < 0. {
+            lonsign = -lonsign;
+            core::mem::swap(&mut lat1, &mut lat2);
+        }
+        let latsign = if lat1 < 0. { 1. } else { -1. };
+        lat1 *= latsign;
+        lat2 *= latsign;
+
+        let (sbet1, cbet1) = reduced_latitude(lat1, self.f1);
+        let (mut sbet2, mut cbet2) = reduced_latitude(lat2, self.f1);
+
+        // keep the points symmetric if they are on the same parallel
+        if cbet1 < -sbet1 {
+            if cbet2 == cbet1 {
+                sbet2 = if sbet2 < 0. { sbet1 } else { -sbet1 };
+            }
+        } else if sbet2.abs() == -sbet1 {
+            cbet2 = cbet1;
+        }
+
+        let dn1 = (1. + self.ep2 * sq(sbet1)).sqrt();
+        let dn2 = (1. + self.ep2 * sq(sbet2)).sqrt();
+
+        let mut c1a = [0.; N + 1];
+        let mut c2a = [0.; N + 1];
+        let mut c3a = [0.; N];
+
+        let (mut salp1, mut calp1, mut salp2, mut calp2) = (0., 0., 0., 0.);
+        let mut s12x = 0.;
+
+        let mut meridian = lat1 == -90. || slam12 == 0.;
+        if meridian {
+            // along a meridian, also if the first point is a pole
+            calp1 = clam12;
+            salp1 = slam12;
+            calp2 = 1.;
+            salp2 = 0.;
+
+            let (ssig1, csig1) = (sbet1, calp1 * cbet1);
+            let (ssig2, csig2) = (sbet2, calp2 * cbet2);
+            let sig12 = ((csig1 * ssig2 - ssig1 * csig2).max(0.) + 0.).atan2(csig1 * csig2 + ssig1 * ssig2);
+            let (s12b, m12b, _) = self.lengths(
+                self.n,
+                sig12,
+                (ssig1, csig1, dn1),
+                (ssig2, csig2, dn2),
+                &mut c1a,
+                &mut c2a,
+            );
+
+            // the meridian is the shortest path if it has no conjugate point
+            if sig12 < 1. || m12b >= 0. {
+                let zero = sig12 < 3. * tiny() || (sig12 < TOL0 && (s12b < 0. || m12b < 0.));
+                s12x = if zero { 0. } else { s12b * self.b };
+            } else {
+                meridian = false;
+            }
+        }
+
+        if !meridian && sbet1 == 0. && (self.f <= 0. || lon12s >= self.f * 180.) {
+            // along the equator
+            calp1 = 0.;
+            calp2 = 0.;
+            salp1 = 1.;
+            salp2 = 1.;
+            s12x = self.a * lam12;
+        } else if !meridian {
+            let (sig12, s1, c1, s2, c2, dnm) =
+                self.inverse_start((sbet1, cbet1), (sbet2, cbet2), lam12, slam12, clam12);
+            salp1 = s1;
+            calp1 = c1;
+
+            if sig12 >= 0. {
+                // short lines
+                salp2 = s2;
+                calp2 = c2;
+                s12x = sig12 * self.b * dnm;
+            } else {
+                // Newton's method, falling back to bisection
+                let mut numit = 0;
+                let (mut tripn, mut tripb) = (false, false);
+                let (mut salp1a, mut calp1a) = (tiny(), 1.);
+                let (mut salp1b, mut calp1b) = (tiny(), -1.);
+
+                let mut l;
+                loop {
+                    l = self.lambda12(
+                        (sbet1, cbet1, dn1),
+                        (sbet2, cbet2, dn2),
+                        salp1,
+                        calp1,
+                        slam12,
+                        clam12,
+                        numit < MAXIT1,
+                        &mut c1a,
+                        &mut c2a,
+                        &mut c3a,
+                    );
+                    let v = l.lam12;
+                    let dv = l.dlam12;
+
+                    if numit >= MAXIT2 || tripb || v.abs() < if tripn { 8. } else { 1. } * TOL0 {
+                        break;
+                    }
+
+                    // update the bracketing values
+                    if v > 0. && (numit > MAXIT1 || calp1 / salp1 > calp1b / salp1b) {
+                        salp1b = salp1;
+                        calp1b = calp1;
+                    } else if v < 0. && (numit > MAXIT1 || calp1 / salp1 < calp1a / salp1a) {
+                        salp1a = salp1;
+                        calp1a = calp1;
+                    }
+
+                    numit += 1;
+                    if numit < MAXIT1 && dv > 0. {
+                        let dalp1 = -v / dv;
+                        let (sdalp1, cdalp1) = (dalp1.sin(), dalp1.cos());
+                        let nsalp1 = salp1 * cdalp1 + calp1 * sdalp1;
+                        if nsalp1 > 0. && dalp1.abs() < PI {
+                            let (s, c) = norm(nsalp1, calp1 * cdalp1 - salp1 * sdalp1);
+                            salp1 = s;
+                            calp1 = c;
+                            tripn = v.abs() <= 16. * TOL0;
+                            continue;
+                        }
+                    }
+
+                    // bisection
+                    let (s, c) = norm((salp1a + salp1b) / 2., (calp1a + calp1b) / 2.);
+                    salp1 = s;
+                    calp1 = c;
+                    tripn = false;
+                    let tolb = TOL0 * tol2();
+                    tripb = (salp1a - salp1).abs() + (calp1a - calp1) < tolb
+                        || (salp1 - salp1b).abs() + (calp1 - calp1b) < tolb;
+                }
+
+                let (s, _, _) = self.lengths(
+                    l.eps,
+                    l.sig12,
+                    (l.sig1.0, l.sig1.1, dn1),
+                    (l.sig2.0, l.sig2.1, dn2),
+                    &mut c1a,
+                    &mut c2a,
+                );
+                s12x = s * self.b;
+                salp2 = l.salp2;
+                calp2 = l.calp2;
+            }
+        }
+
+        let s12 = s12x + 0.;
+
+        if swapp < 0. {
+            core::mem::swap(&mut salp1, &mut salp2);
+            core::mem::swap(&mut calp1, &mut calp2);
+        }
+
+        salp1 *= swapp * lonsign;
+        calp1 *= swapp * latsign;
+        salp2 *= swapp * lonsign;
+        calp2 *= swapp * latsign;
+
+        (s12, salp1, calp1, salp2, calp2)
+    }
+}
+
+/// Result of `Geodesic::lambda12()`
+struct Lambda12 {
+    lam12: f64,
+    salp2: f64,
+    calp2: f64,
+    sig12: f64,
+    sig1: (f64, f64),
+    sig2: (f64, f64),
+    eps: f64,
+    dlam12: f64,
+}
+
+/// Great circle distance and bearings on a sphere of the mean Earth radius (the haversine formula).
+///
+/// Faster than `Geodesic::inverse()`, with an error of up to about 0.6 % of the distance.
+pub fn haversine(p1: Point<f64>, p2: Point<f64>) -> GeodesicInverse {
+    let (phi1, phi2) = (p1.y().to_radians(), p2.y().to_radians());
+    let dphi = phi2 - phi1;
+    let dlambda = (p2.x() - p1.x()).to_radians();
+
+    let h = sq((dphi / 2.).sin()) + phi1.cos() * phi2.cos() * sq((dlambda / 2.).sin());
+    let distance = 2. * EARTH_RADIUS * h.sqrt().atan2((1. - h).max(0.).sqrt());
+
+    let bearing = |phi1: f64, phi2: f64, dlambda: f64| {
+        let y = dlambda.sin() * phi2.cos();
+        let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * dlambda.cos();
+        y.atan2(x).to_degrees()
+    };
+
+    // the final bearing is the reversed initial bearing from the second point
+    let reverse = bearing(phi2, phi1, -dlambda);
+    GeodesicInverse {
+        distance,
+        initial_azimuth: bearing(phi1, phi2, dlambda),
+        final_azimuth: if reverse > 0. { reverse - 180. } else { reverse + 180. },
+    }
+}
+
+/// Destination at a distance (m) from a point at a bearing (degrees) along a great circle
+/// on a sphere of the mean Earth radius
+pub fn great_circle_destination(point: Point<f64>, bearing: f64, distance: f64) -> Point<f64> {
+    let (phi1, lambda1) = (point.y().to_radians(), point.x().to_radians());
+    let theta = bearing.to_radians();
+    let delta = distance / EARTH_RADIUS;
+
+    let sin_phi2 = phi1.sin() * delta.cos() + phi1.cos() * delta.sin() * theta.cos();
+    let phi2 = sin_phi2.asin();
+    let y = theta.sin() * delta.sin() * phi1.cos();
+    let x = delta.cos() - phi1.sin() * sin_phi2;
+    let lambda2 = lambda1 + y.atan2(x);
+
+    Point::new(ang_normalize(lambda2.to_degrees()), phi2.to_degrees())
+}
+
+/// Point at a fraction of the great circle between two points on a sphere
+/// (0 is the first point, 1 the second)
+pub fn great_circle_intermediate(p1: Point<f64>, p2: Point<f64>, fraction: f64) -> Point<f64> {
+    let path = haversine(p1, p2);
+    great_circle_destination(p1, path.initial_azimuth, path.distance * fraction)
+}
+
+/// Point halfway along the great circle between two points on a sphere
+pub fn great_circle_midpoint(p1: Point<f64>, p2: Point<f64>) -> Point<f64> {
+    great_circle_intermediate(p1, p2, 0.5)
+}
+
+fn sq(x: f64) -> f64 {
+    x * x
+}
+
+/// Evaluate a polynomial, with the coefficients from the highest order
+fn polyval(p: &[f64], x: f64) -> f64 {
+    p.iter().fold(0., |y, &c| y * x + c)
+}
+
+fn norm(x: f64, y: f64) -> (f64, f64) {
+    let r = x.hypot(y);
+    (x / r, y / r)
+}
+
+/// Sine and cosine of the reduced latitude (degrees), given `1 - f`
+fn reduced_latitude(lat: f64, f1: f64) -> (f64, f64) {
+    let (s, c) = sincosd(lat);
+    let (s, c) = norm(s * f1, c);
+    (s, c.max(tiny()))
+}
+
+/// Round an angle so that small values are exact
+fn ang_round(x: f64) -> f64 {
+    let z = 1. / 16.;
+    let y = x.abs();
+    let w = z - y;
+    let y = if w > 0. { z - w } else { y };
+    if x.is_sign_negative() {
+        -y
+    } else {
+        y
+    }
+}
+
+fn lat_fix(x: f64) -> f64 {
+    if x.abs() > 90. {
+        f64::NAN
+    } else {
+        x
+    }
+}
+
+/// Remainder of division by 360, in the range -180 to 180
+fn ang_remainder(x: f64) -> f64 {
+    let y = x % 360.;
+    if y < -180. {
+        y + 360.
+    } else if y > 180. {
+        y - 360.
+    } else {
+        y
+    }
+}
+
+/// Reduce an angle to the range -180 (exclusive) to 180
+fn ang_normalize(x: f64) -> f64 {
+    let y = ang_remainder(x);
+    if y == -180. {
+        180.
+    } else {
+        y
+    }
+}
+
+/// Error-free sum, returns the sum and the rounding error
+fn sum(u: f64, v: f64) -> (f64, f64) {
+    let s = u + v;
+    let up = s - v;
+    let vpp = s - up;
+    let t = -((up - u) + (vpp - v));
+    (s, if s == 0. { s } else { t })
+}
+
+/// Exact difference of two longitudes, returns the difference reduced to -180 to 180
+/// and its rounding error
+fn ang_diff(x: f64, y: f64) -> (f64, f64) {
+    let (d, t) = sum(ang_remainder(-x), ang_remainder(y));
+    let mut d = ang_remainder(d);
+    if d == 0. || d.abs() == 180. {
+        let sign = if t == 0. { y - x } else { -t };
+        d = if sign.is_sign_negative() { -d.abs() } else { d.abs() };
+    }
+    sum(d, t)
+}
+
+/// Sine and cosine of an angle in degrees, exact for multiples of 90°
+fn sincosd(x: f64) -> (f64, f64) {
+    let r = x % 360.;
+    let q = if r.is_nan() { 0 } else { (r / 90.).round() as i32 };
+    let r = (r - 90. * f64::from(q)).to_radians();
+    let (s, c) = (r.sin(), r.cos());
+    let (s, c) = match q.rem_euclid(4) {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    };
+    let s = if s == 0. && x.is_sign_negative() { -0. } else { s + 0. };
+    (s, c + 0.)
+}
+
+/// Angle in degrees, exact for multiples of 45°
+fn atan2d(y: f64, x: f64) -> f64 {
+    let (mut y, mut x) = (y, x);
+    let mut q = 0;
+    if y.abs() > x.abs() {
+        core::mem::swap(&mut x, &mut y);
+        q = 2;
+    }
+    if x < 0. {
+        x = -x;
+        q += 1;
+    }
+    let ang = y.atan2(x).to_degrees();
+    match q {
+        1 => (if y >= 0. { 180. } else { -180. }) - ang,
+        2 => 90. - ang,
+        3 => -90. + ang,
+        _ => ang,
+    }
+}
+
+/// Solve the astroid equation `k⁴ + 2k³ - (x² + y² - 1)k² - 2y²k - y² = 0` for the positive root
+fn astroid(x: f64, y: f64) -> f64 {
+    let p = sq(x);
+    let q = sq(y);
+    let r = (p + q - 1.) / 6.;
+    if q == 0. && r <= 0. {
+        return 0.;
+    }
+
+    let s = p * q / 4.;
+    let r2 = sq(r);
+    let r3 = r * r2;
+    let disc = s * (s + 2. * r3);
+    let mut u = r;
+    if disc >= 0. {
+        let mut t3 = s + r3;
+        t3 += if t3 < 0. { -disc.sqrt() } else { disc.sqrt() };
+        let t = t3.cbrt();
+        u += t + if t != 0. { r2 / t } else { 0. };
+    } else {
+        let ang = (-disc).sqrt().atan2(-(s + r3));
+        u += 2. * r * (ang / 3.).cos();
+    }
+
+    let v = (sq(u) + q).sqrt();
+    let uv = if u < 0. { q / (v - u) } else { u + v };
+    let w = (uv - q) / (2. * v);
+    uv / ((uv + sq(w)).sqrt() + w)
+}
+
+/// Evaluate `sum(c[i] * sin(2i * x))` (`sinp`) or `sum(c[i] * cos((2i + 1) * x))` by Clenshaw summation
+fn sin_cos_series(sinp: bool, sinx: f64, cosx: f64, c: &[f64]) -> f64 {
+    let mut k = c.len();
+    let mut n = k - usize::from(sinp);
+    let ar = 2. * (cosx - sinx) * (cosx + sinx);
+    let mut y1 = 0.;
+    let mut y0 = if n % 2 == 1 {
+        k -= 1;
+        c[k]
+    } else {
+        0.
+    };
+
+    n /= 2;
+    while n > 0 {
+        n -= 1;
+        k -= 1;
+        y1 = ar * y0 - y1 + c[k];
+        k -= 1;
+        y0 = ar * y1 - y0 + c[k];
+    }
+
+    if sinp {
+        2. * sinx * cosx * y0
+    } else {
+        cosx * (y0 - y1)
+    }
+}
+
+/// Evaluate the series with coefficients `coeff`, grouped by order as polynomials of `eps²`
+/// followed by their divisor, to `c[1..=N]`
+fn series(coeff: &[f64], eps: f64, c: &mut [f64; N + 1]) {
+    let eps2 = sq(eps);
+    let mut d = eps;
+    let mut o = 0;
+    for (l, c) in c.iter_mut().enumerate().skip(1) {
+        let m = (N - l) / 2;
+        *c = d * polyval(&coeff[o..=o + m], eps2) / coeff[o + m + 1];
+        o += m + 2;
+        d *= eps;
+    }
+}
+
+/// `A1 - 1`
+fn a1m1f(eps: f64) -> f64 {
+    let coeff = [1., 4., 64., 0., 256.];
+    let t = polyval(&coeff[..4], sq(eps)) / coeff[4];
+    (t + eps) / (1. - eps)
+}
+
+fn c1f(eps: f64, c: &mut [f64; N + 1]) {
+    #[rustfmt::skip]
+    let coeff = [
+        -1., 6., -16., 32.,
+        -9., 64., -128., 2048.,
+        9., -16., 768.,
+        3., -5., 512.,
+        -7., 1280.,
+        -7., 2048.,
+    ];
+    series(&coeff, eps, c);
+}
+
+/// Coefficients of the reverted `C1` series
+fn c1pf(eps: f64, c: &mut [f64; N + 1]) {
+    #[rustfmt::skip]
+    let coeff = [
+        205., -432., 768., 1536.,
+        4005., -4736., 3840., 12288.,
+        -225., 116., 384.,
+        -7173., 2695., 7680.,
+        3467., 7680.,
+        38081., 61440.,
+    ];
+    series(&coeff, eps, c);
+}
+
+/// `A2 - 1`
+fn a2m1f(eps: f64) -> f64 {
+    let coeff = [-11., -28., -192., 0., 256.];
+    let t = polyval(&coeff[..4], sq(eps)) / coeff[4];
+    (t - eps) / (1. + eps)
+}
+
+fn c2f(eps: f64, c: &mut [f64; N + 1]) {
+    #[rustfmt::skip]
+    let coeff = [
+        1., 2., 16., 32.,
+        35., 64., 384., 2048.,
+        15., 80., 768.,
+        7., 35., 512.,
+        63., 1280.,
+        77., 2048.,
+    ];
+    series(&coeff, eps, c);
+}
+
+/// Coefficients of `A3` as a polynomial of `eps`, from the third flattening
+fn a3_coeff(n: f64) -> [f64; N] {
+    #[rustfmt::skip]
+    let coeff = [
+        -3., 128.,
+        -2., -3., 64.,
+        -1., -3., -1., 16.,
+        3., -1., -2., 8.,
+        1., -1., 2.,
+        1., 1.,
+    ];
+    let mut a3x = [0.; N];
+    let mut o = 0;
+    for (k, j) in (0..N).rev().enumerate() {
+        let m = (N - j - 1).min(j);
+        a3x[k] = polyval(&coeff[o..=o + m], n) / coeff[o + m + 1];
+        o += m + 2;
+    }
+    a3x
+}
+
+/// Coefficients of the `C3` series as polynomials of `eps`, from the third flattening
+fn c3_coeff(n: f64) -> [f64; N_C3X] {
+    #[rustfmt::skip]
+    let coeff = [
+        3., 128.,
+        2., 5., 128.,
+        -1., 3., 3., 64.,
+        -1., 0., 1., 8.,
+        -1., 1., 4.,
+        5., 256.,
+        1., 3., 128.,
+        -3., -2., 3., 64.,
+        1., -3., 2., 32.,
+        7., 512.,
+        -10., 9., 384.,
+        5., -9., 5., 192.,
+        7., 512.,
+        -14., 7., 512.,
+        21., 2560.,
+    ];
+    let mut c3x = [0.; N_C3X];
+    let mut o = 0;
+    let mut k = 0;
+    for l in 1..N {
+        for j in (l..N).rev() {
+            let m = (N - j - 1).min(j);
+            c3x[k] = polyval(&coeff[o..=o + m], n) / coeff[o + m + 1];
+            k += 1;
+            o += m + 2;
+        }
+    }
+    c3x
+}
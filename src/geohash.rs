@@ -0,0 +1,73 @@
+//! Geohash encoding
+
+use crate::Point;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+/// Geohash alphabet (base 32 without `a`, `i`, `l`, `o`)
+const ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Maximum length of a geohash (cells of about 37 mm × 19 mm)
+const MAX_LEN: usize = 12;
+
+/// Geohash of a point, e.g. `dppn5fyzh`, stored inline.
+///
+/// The `Display` implementation writes the hash string.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Geohash {
+    buf: [u8; MAX_LEN],
+    len: usize,
+}
+
+impl Geohash {
+    /// Encode a point with the given number of characters (1 to 12).
+    /// Returns `None` if the length or the coordinates are out of range.
+    pub fn encode(point: Point<f64>, len: usize) -> Option<Geohash> {
+        let (lat, lng) = (point.y(), point.x());
+        if len == 0 || len > MAX_LEN || !(-90f64..=90f64).contains(&lat) || !(-180f64..=180f64).contains(&lng) {
+            return None;
+        }
+
+        let mut lat_range = (-90f64, 90f64);
+        let mut lng_range = (-180f64, 180f64);
+        let mut even = true;
+        let mut buf = [0u8; MAX_LEN];
+
+        for c in buf.iter_mut().take(len) {
+            let mut index = 0;
+            for _ in 0..5 {
+                // bits alternate between longitude and latitude, starting with longitude
+                let (range, value) = if even {
+                    (&mut lng_range, lng)
+                } else {
+                    (&mut lat_range, lat)
+                };
+
+                let mid = (range.0 + range.1) / 2.;
+                index <<= 1;
+                if value >= mid {
+                    index |= 1;
+                    range.0 = mid;
+                } else {
+                    range.1 = mid;
+                }
+                even = !even;
+            }
+            *c = ALPHABET[index];
+        }
+
+        Some(Geohash { buf, len })
+    }
+
+    /// Get the hash as string
+    pub fn as_str(&self) -> &str {
+        // the buffer is filled from the ASCII alphabet
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Display for Geohash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,236 @@
+//! Gauss-Krüger grid coordinates, with the zone number prefixed to the easting
+
+use crate::datum::Datum;
+use crate::tm::TransverseMercator;
+use crate::{grid, GeoParseError, Point};
+use core::fmt;
+use core::fmt::{Display, Formatter};
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Maximum distance of a point from the central meridian (m), a few times the usual zone width
+const MAX_OFFSET: f64 = 400_000.;
+
+/// Geodetic system of Gauss-Krüger coordinates, which sets the datum and the zone width
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum GaussKrugerSystem {
+    /// German DHDN, 3° zones numbered by the central meridian (zone 4 is 12° E)
+    #[default]
+    Dhdn,
+    /// S-42 of the former Eastern Bloc, 6° zones numbered from Greenwich (zone 4 is 21° E)
+    S42,
+}
+
+impl GaussKrugerSystem {
+    /// Get the datum of coordinates in this system
+    pub fn datum(self) -> Datum {
+        match self {
+            GaussKrugerSystem::Dhdn => Datum::Dhdn,
+            GaussKrugerSystem::S42 => Datum::S42,
+        }
+    }
+
+    /// Zone width (degrees)
+    fn zone_width(self) -> f64 {
+        match self {
+            GaussKrugerSystem::Dhdn => 3.,
+            GaussKrugerSystem::S42 => 6.,
+        }
+    }
+
+    /// Number of zones around the globe
+    fn zone_count(self) -> u32 {
+        (360. / self.zone_width()) as u32
+    }
+
+    /// Central meridian of a zone (degrees, -180 to 180)
+    fn central_meridian(self, zone: u32) -> f64 {
+        let lng = match self {
+            GaussKrugerSystem::Dhdn => f64::from(zone) * 3.,
+            GaussKrugerSystem::S42 => f64::from(zone) * 6. - 3.,
+        };
+        if lng > 180. {
+            lng - 360.
+        } else {
+            lng
+        }
+    }
+
+    /// Get the zone of a longitude (degrees)
+    fn zone(self, lng: f64) -> u32 {
+        let lng = if lng < 0. { lng + 360. } else { lng };
+        let zone = match self {
+            GaussKrugerSystem::Dhdn => (lng / 3.).round() as u32,
+            GaussKrugerSystem::S42 => (lng / 6.).floor() as u32 + 1,
+        };
+        match zone {
+            0 => self.zone_count(),
+            zone if zone > self.zone_count() => zone - self.zone_count(),
+            zone => zone,
+        }
+    }
+
+    fn projection(self, zone: u32) -> TransverseMercator {
+        TransverseMercator {
+            ellipsoid: self.datum().ellipsoid(),
+            k0: 1.,
+            lng0: self.central_meridian(zone),
+            lat0: 0.,
+            false_easting: f64::from(zone) * 1_000_000. + 500_000.,
+            false_northing: 0.,
+        }
+    }
+}
+
+/// Gauss-Krüger coordinates, e.g. `R 4468012 H 5333289` (Rechtswert and Hochwert).
+///
+/// The easting is prefixed with the zone number (millions of meters), and includes the false easting
+/// of 500 km.
+///
+/// The `Display` implementation writes `R 4468012 H 5333289`, rounded to meters by default (precision `{:.1}`
+/// applies to both numbers). The alternate form (`{:#}`) writes just the numbers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GaussKruger {
+    /// Geodetic system
+    pub system: GaussKrugerSystem,
+    /// Easting with the zone prefix, Rechtswert (m)
+    pub easting: f64,
+    /// Northing, Hochwert (m)
+    pub northing: f64,
+}
+
+impl GaussKruger {
+    /// Create from easting and northing (no range checking is done)
+    pub fn new(system: GaussKrugerSystem, easting: f64, northing: f64) -> GaussKruger {
+        GaussKruger {
+            system,
+            easting,
+            northing,
+        }
+    }
+
+    /// Get the zone number from the easting prefix
+    pub fn zone(&self) -> u32 {
+        (self.easting / 1_000_000.).floor().max(0.) as u32
+    }
+
+    /// Project a WGS84 point in the zone of its longitude. Returns `None` in the southern hemisphere
+    /// or if the point is out of range.
+    pub fn from_point(point: Point<f64>, system: GaussKrugerSystem) -> Option<GaussKruger> {
+        if !(0f64..=84f64).contains(&point.y()) || !(-180f64..=180f64).contains(&point.x()) {
+            return None;
+        }
+
+        let local = Datum::Wgs84.transform(point, system.datum());
+        let zone = system.zone(local.x());
+        let (easting, northing) = system.projection(zone).forward(local.y(), local.x());
+        Some(GaussKruger::new(system, easting, northing))
+    }
+
+    /// Get the WGS84 point
+    pub fn to_point(&self) -> Point<f64> {
+        let (lat, lng) = self.system.projection(self.zone()).inverse(self.easting, self.northing);
+        self.system.datum().to_wgs84(Point::new(lng, lat))
+    }
+
+    /// Parse Gauss-Krüger coordinates of the system.
+    ///
+    /// The numbers can be labeled, in any order, by `R` / `RW` / `Rechtswert` / `E` and `H` / `HW` /
+    /// `Hochwert` / `N` (case-insensitive, optionally followed by `:` or `=`), e.g. `R 4468012 H 5333289`,
+    /// `Rechtswert: 4468012, Hochwert: 5333289`. Numbers without labels are taken as easting and northing,
+    /// e.g. `4468012 5333289`.
+    ///
+    /// The zone of the easting prefix must exist in the system, and the coordinates must be within
+    /// a few hundred kilometers of its central meridian, in the northern hemisphere.
+    pub fn parse<T: AsRef<str> + Display>(text: T, system: GaussKrugerSystem) -> Result<GaussKruger, GeoParseError<T>> {
+        match do_parse_gk(text.as_ref(), system) {
+            Some(p) => Ok(p),
+            None => Err(GeoParseError(text)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Axis {
+    Easting,
+    Northing,
+}
+
+/// Take a label from the start of the text, returning the rest after an optional `:` or `=`
+fn take_label(s: &str) -> Option<(Axis, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let label = &s[..end];
+    let is_any = |labels: &[&str]| labels.iter().any(|l| l.eq_ignore_ascii_case(label));
+
+    let axis = if is_any(&["r", "rw", "rechtswert", "e", "easting"]) {
+        Axis::Easting
+    } else if is_any(&["h", "hw", "hochwert", "n", "northing"]) {
+        Axis::Northing
+    } else {
+        return None;
+    };
+
+    let rest = s[end..].trim_start();
+    let rest = rest.strip_prefix([':', '=']).unwrap_or(rest);
+    Some((axis, rest.trim_start()))
+}
+
+/// Take a number from the start of the text, returning it and the rest after an optional separator
+fn take_number(s: &str) -> Option<(f64, &str)> {
+    let end = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ',' || c == '+' || c == '-'))
+        .unwrap_or(s.len());
+    let number = s[..end].trim_end_matches(',');
+    let rest = s[end..].trim_start();
+    let rest = rest.strip_prefix(';').unwrap_or(rest);
+
+    Some((grid::parse_decimal(number)?, rest.trim_start()))
+}
+
+fn do_parse_gk(s: &str, system: GaussKrugerSystem) -> Option<GaussKruger> {
+    let s = s.trim();
+
+    let (easting, northing) = if s.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        let (first_axis, rest) = take_label(s)?;
+        let (first, rest) = take_number(rest)?;
+        let (second_axis, rest) = take_label(rest)?;
+        let (second, rest) = take_number(rest)?;
+        if first_axis == second_axis || !rest.is_empty() {
+            return None;
+        }
+
+        if first_axis == Axis::Easting {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    } else {
+        grid::parse_pair(s)?
+    };
+
+    let gk = GaussKruger::new(system, easting, northing);
+    let zone = gk.zone();
+    let offset = easting - f64::from(zone) * 1_000_000. - 500_000.;
+    if zone == 0 || zone > system.zone_count() || offset.abs() > MAX_OFFSET || !(0f64..10_000_000f64).contains(&northing) {
+        return None;
+    }
+
+    Some(gk)
+}
+
+impl From<GaussKruger> for Point<f64> {
+    fn from(gk: GaussKruger) -> Self {
+        gk.to_point()
+    }
+}
+
+impl Display for GaussKruger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(0);
+        if f.alternate() {
+            write!(f, "{:.*} {:.*}", precision, self.easting, precision, self.northing)
+        } else {
+            write!(f, "R {:.*} H {:.*}", precision, self.easting, precision, self.northing)
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! GPX waypoints, route points and track points
+
+use crate::xml::{next_tag, parse_number, text, Tag};
+use crate::{ParseError, ParseErrorKind, Point};
+#[cfg(feature = "alloc")]
+use crate::xml::push_escaped;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use core::fmt::Write;
+
+/// Kind of a point read from GPX or KML
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum PointKind {
+    /// GPX waypoint (`wpt`)
+    #[default]
+    Waypoint,
+    /// GPX route point (`rtept`)
+    RoutePoint,
+    /// GPX track point (`trkpt`)
+    TrackPoint,
+    /// KML coordinate tuple (in `coordinates`)
+    Coordinate,
+}
+
+/// Point read from GPX or KML, borrowing from the document
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathPoint<'a> {
+    /// Kind of the point
+    pub kind: PointKind,
+    /// Position (WGS84)
+    pub point: Point<f64>,
+    /// Elevation or altitude (m)
+    pub elevation: Option<f64>,
+    /// Time as written in the document (ISO 8601 in GPX), e.g. `2024-05-01T08:30:00Z`
+    pub time: Option<&'a str>,
+}
+
+impl From<Point<f64>> for PathPoint<'_> {
+    fn from(point: Point<f64>) -> Self {
+        PathPoint {
+            kind: PointKind::default(),
+            point,
+            elevation: None,
+            time: None,
+        }
+    }
+}
+
+impl From<PathPoint<'_>> for Point<f64> {
+    fn from(p: PathPoint<'_>) -> Self {
+        p.point
+    }
+}
+
+/// Read the points of a GPX document, see `GpxPoints`.
+///
+/// ```
+/// let gpx = r#"<gpx><trk><trkseg>
+///     <trkpt lat="50.08" lon="14.42"><ele>235.5</ele><time>2024-05-01T08:30:00Z</time></trkpt>
+/// </trkseg></trk></gpx>"#;
+///
+/// let p = latlon::read_gpx(gpx).next().unwrap().unwrap();
+/// assert_eq!((14.42, 50.08), p.point.x_y());
+/// assert_eq!((Some(235.5), Some("2024-05-01T08:30:00Z")), (p.elevation, p.time));
+/// ```
+pub fn read_gpx(doc: &str) -> GpxPoints<'_> {
+    GpxPoints { doc, pos: 0 }
+}
+
+/// Iterator of the waypoints (`wpt`), route points (`rtept`) and track points (`trkpt`) of a GPX
+/// document, in document order, returned by `read_gpx()`.
+///
+/// The document is scanned as it is iterated, without building a tree or allocating. Only
+/// the position, elevation (`ele`) and time of the points are read; other elements are skipped, and
+/// the document is not validated. A point with invalid or missing coordinates gives an error with its
+/// span in the document, and the iteration can continue with the next point.
+#[derive(Debug, Clone)]
+pub struct GpxPoints<'a> {
+    doc: &'a str,
+    pos: usize,
+}
+
+impl<'a> GpxPoints<'a> {
+    fn read_point(&mut self, tag: &Tag<'a>, kind: PointKind) -> Result<PathPoint<'a>, ParseError> {
+        let coordinate = |name: &str, limit: f64| {
+            let (value, offset) =
+                tag.attribute(name).ok_or(ParseError::new(ParseErrorKind::Format, tag.start, tag.end))?;
+            let error = |kind| ParseError::new(kind, offset, offset + value.len());
+            let v = parse_number(value).ok_or(error(ParseErrorKind::InvalidNumber))?;
+            if v.abs() > limit {
+                return Err(error(ParseErrorKind::OutOfRange));
+            }
+            Ok(v)
+        };
+        let point = Point::new(coordinate("lon", 180.)?, coordinate("lat", 90.)?);
+
+        let mut p = PathPoint {
+            kind,
+            point,
+            elevation: None,
+            time: None,
+        };
+        if tag.empty {
+            return Ok(p);
+        }
+
+        loop {
+            let child = match next_tag(self.doc, self.pos) {
+                Some(child) => child,
+                None => {
+                    self.pos = self.doc.len();
+                    return Err(ParseError::new(ParseErrorKind::Format, tag.start, self.doc.len()));
+                }
+            };
+            self.pos = child.end;
+
+            if child.closing && child.name == tag.name {
+                return Ok(p);
+            } else if child.closing || child.empty {
+                continue;
+            }
+
+            let (value, offset) = text(self.doc, &child);
+            match child.name {
+                "ele" => {
+                    let error = ParseError::new(ParseErrorKind::InvalidNumber, offset, offset + value.len());
+                    p.elevation = Some(parse_number(value).ok_or(error)?);
+                }
+                "time" => p.time = Some(value),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for GpxPoints<'a> {
+    type Item = Result<PathPoint<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let tag = next_tag(self.doc, self.pos)?;
+            self.pos = tag.end;
+
+            let kind = match tag.name {
+                _ if tag.closing => continue,
+                "wpt" => PointKind::Waypoint,
+                "rtept" => PointKind::RoutePoint,
+                "trkpt" => PointKind::TrackPoint,
+                _ => continue,
+            };
+            return Some(self.read_point(&tag, kind));
+        }
+    }
+}
+
+/// Write a minimal GPX 1.1 document with the points as waypoints, a route or a track
+/// (`PointKind::Coordinate` also makes a track).
+///
+/// The kind of `PathPoint` items is ignored. Their elevation and time are written if set,
+/// the time should be in ISO 8601 (e.g. `2024-05-01T08:30:00Z`).
+///
+/// ```
+/// use latlon::{write_gpx, Point, PointKind};
+///
+/// let gpx = write_gpx(vec![Point::new(14.42, 50.08)], PointKind::Waypoint);
+/// assert!(gpx.contains(r#"<wpt lat="50.08" lon="14.42"/>"#));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn write_gpx<'a, I>(points: I, kind: PointKind) -> String
+where
+    I: IntoIterator,
+    I::Item: Into<PathPoint<'a>>,
+{
+    let (open, element, close) = match kind {
+        PointKind::Waypoint => ("", "wpt", ""),
+        PointKind::RoutePoint => ("<rte>\n", "rtept", "</rte>\n"),
+        PointKind::TrackPoint | PointKind::Coordinate => ("<trk><trkseg>\n", "trkpt", "</trkseg></trk>\n"),
+    };
+
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<gpx version=\"1.1\" creator=\"latlon\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n",
+    ));
+    out.push_str(open);
+
+    for p in points {
+        let p = p.into();
+        // writing to a String cannot fail
+        let _ = write!(out, "<{} lat=\"{}\" lon=\"{}\"", element, p.point.y(), p.point.x());
+        if p.elevation.is_none() && p.time.is_none() {
+            out.push_str("/>\n");
+            continue;
+        }
+
+        out.push('>');
+        if let Some(ele) = p.elevation {
+            let _ = write!(out, "<ele>{}</ele>", ele);
+        }
+        if let Some(time) = p.time {
+            out.push_str("<time>");
+            push_escaped(&mut out, time);
+            out.push_str("</time>");
+        }
+        let _ = writeln!(out, "</{}>", element);
+    }
+
+    out.push_str(close);
+    out.push_str("</gpx>\n");
+    out
+}
@@ -0,0 +1,61 @@
+//! Helpers for parsing projected (grid) coordinates
+
+/// Maximum length of a number with decimal comma, which is copied to a stack buffer to be parsed
+const COMMA_NUMBER_LEN: usize = 64;
+
+/// Split text to two numbers, separated by semicolon, slash, whitespace (optionally with a comma) or comma.
+///
+/// Comma is taken as a decimal separator if the numbers are separated otherwise.
+pub(crate) fn split_pair(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim();
+
+    let (first, second) = if let Some(i) = s.find([';', '/']) {
+        (&s[..i], &s[i + 1..])
+    } else if let Some(i) = s.find(char::is_whitespace) {
+        let first = s[..i].trim_end();
+        let second = s[i..].trim_start();
+        match (first.strip_suffix(','), second.strip_prefix(',')) {
+            (Some(first), _) => (first, second),
+            (None, Some(second)) => (first, second),
+            (None, None) => (first, second),
+        }
+    } else {
+        let i = s.find(',')?;
+        (&s[..i], &s[i + 1..])
+    };
+
+    Some((first.trim(), second.trim()))
+}
+
+/// Parse a decimal number with an optional sign, accepting comma as decimal separator.
+/// Exponents and other forms accepted by `f64::from_str` are rejected.
+pub(crate) fn parse_decimal(s: &str) -> Option<f64> {
+    let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+    let (int, frac) = match digits.find(['.', ',']) {
+        Some(i) => (&digits[..i], Some(&digits[i + 1..])),
+        None => (digits, None),
+    };
+
+    let valid = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !valid(int) || !frac.map_or(true, valid) {
+        return None;
+    }
+
+    if !s.contains(',') {
+        return s.parse().ok();
+    }
+
+    let mut buf = [0u8; COMMA_NUMBER_LEN];
+    let buf = buf.get_mut(..s.len())?;
+    for (b, c) in buf.iter_mut().zip(s.bytes()) {
+        *b = if c == b',' { b'.' } else { c };
+    }
+
+    core::str::from_utf8(buf).ok()?.parse().ok()
+}
+
+/// Parse two numbers, see `split_pair()`
+pub(crate) fn parse_pair(s: &str) -> Option<(f64, f64)> {
+    let (first, second) = split_pair(s)?;
+    Some((parse_decimal(first)?, parse_decimal(second)?))
+}
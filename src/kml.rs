@@ -0,0 +1,143 @@
+//! KML coordinate tuples
+
+use crate::gpx::{PathPoint, PointKind};
+use crate::xml::{next_tag, parse_number, text};
+use crate::{ParseError, ParseErrorKind, Point};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use core::fmt::Write;
+
+/// Read the coordinate tuples of a KML document, see `KmlPoints`.
+///
+/// ```
+/// let kml = r#"<kml><Placemark><LineString>
+///     <coordinates>14.42,50.08,235 16.61,49.19</coordinates>
+/// </LineString></Placemark></kml>"#;
+///
+/// let points: Vec<_> = latlon::read_kml(kml).collect::<Result<_, _>>().unwrap();
+/// assert_eq!((14.42, 50.08), points[0].point.x_y());
+/// assert_eq!((Some(235.), None), (points[0].elevation, points[1].elevation));
+/// ```
+pub fn read_kml(doc: &str) -> KmlPoints<'_> {
+    KmlPoints {
+        doc,
+        pos: 0,
+        coordinates_end: 0,
+    }
+}
+
+/// Iterator of the coordinate tuples (`lon,lat[,alt]`) in all `coordinates` elements of a KML document,
+/// in document order, returned by `read_kml()`.
+///
+/// The document is scanned as it is iterated, without building a tree or allocating. The points
+/// have `PointKind::Coordinate`, altitude as elevation, and no time. An invalid tuple gives an error
+/// with its span in the document, and the iteration can continue with the next tuple.
+#[derive(Debug, Clone)]
+pub struct KmlPoints<'a> {
+    doc: &'a str,
+    pos: usize,
+    /// End of the text of the current `coordinates` element
+    coordinates_end: usize,
+}
+
+fn parse_tuple(tuple: &str, offset: usize) -> Result<PathPoint<'static>, ParseError> {
+    let error = |kind| ParseError::new(kind, offset, offset + tuple.len());
+
+    let mut numbers = [0f64; 3];
+    let mut count = 0;
+    for part in tuple.split(',') {
+        *numbers.get_mut(count).ok_or(error(ParseErrorKind::TooLong))? =
+            parse_number(part).ok_or(error(ParseErrorKind::InvalidNumber))?;
+        count += 1;
+    }
+    if count < 2 {
+        return Err(error(ParseErrorKind::Format));
+    }
+
+    let [lng, lat, alt] = numbers;
+    if lat.abs() > 90. || lng.abs() > 180. {
+        return Err(error(ParseErrorKind::OutOfRange));
+    }
+
+    Ok(PathPoint {
+        kind: PointKind::Coordinate,
+        point: Point::new(lng, lat),
+        elevation: if count == 3 { Some(alt) } else { None },
+        time: None,
+    })
+}
+
+impl Iterator for KmlPoints<'_> {
+    type Item = Result<PathPoint<'static>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.pos < self.coordinates_end {
+                let rest = &self.doc[self.pos..self.coordinates_end];
+                let trimmed = rest.trim_start();
+                if !trimmed.is_empty() {
+                    let start = self.pos + rest.len() - trimmed.len();
+                    let len = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+                    self.pos = start + len;
+                    return Some(parse_tuple(&trimmed[..len], start));
+                }
+            }
+
+            let tag = next_tag(self.doc, self.pos)?;
+            self.pos = tag.end;
+            if tag.name == "coordinates" && !tag.closing && !tag.empty {
+                let (value, offset) = text(self.doc, &tag);
+                self.coordinates_end = offset + value.len();
+            }
+        }
+    }
+}
+
+/// Write a minimal KML 2.2 document with one placemark: a point, or a line string of two or more
+/// points. With no points, the document is empty.
+///
+/// Elevation of `PathPoint` items is written as altitude, if set.
+///
+/// ```
+/// use latlon::{write_kml, Point};
+///
+/// let kml = write_kml(vec![Point::new(14.42, 50.08), Point::new(16.61, 49.19)]);
+/// assert!(kml.contains("<coordinates>14.42,50.08 16.61,49.19</coordinates>"));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn write_kml<'a, I>(points: I) -> String
+where
+    I: IntoIterator,
+    I::Item: Into<PathPoint<'a>>,
+{
+    let mut coordinates = String::new();
+    let mut count = 0;
+    for p in points {
+        let p = p.into();
+        if count > 0 {
+            coordinates.push(' ');
+        }
+        // writing to a String cannot fail
+        let _ = write!(coordinates, "{},{}", p.point.x(), p.point.y());
+        if let Some(ele) = p.elevation {
+            let _ = write!(coordinates, ",{}", ele);
+        }
+        count += 1;
+    }
+
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<kml xmlns=\"http://www.opengis.net/kml/2.2\">\n",
+    ));
+    if count > 0 {
+        let geometry = if count == 1 { "Point" } else { "LineString" };
+        let _ = writeln!(
+            out,
+            "<Placemark><{0}><coordinates>{1}</coordinates></{0}></Placemark>",
+            geometry, coordinates
+        );
+    }
+    out.push_str("</kml>\n");
+    out
+}
@@ -1,296 +1,74 @@
-#[macro_use]
-extern crate lazy_static;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-// re-export Point so `geo_types` do not have to be added as a dependency
-// to store the `parse()` function's result.
-pub use geo_types::Point;
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
-use regex::Regex;
-use std::convert::TryFrom;
-use std::fmt::Display;
-use std::num::ParseFloatError;
+// re-export the geometry types so `geo_types` do not have to be added as a dependency
+// to store the results of `parse()`, `parse_bbox()` and the list parsers.
+pub use geo_types::{LineString, Point, Polygon, Rect};
 
-#[cfg(test)]
+use core::fmt::Display;
+
+#[cfg(all(test, feature = "std"))]
 mod tests;
+mod bbox;
+mod datum;
 mod errors;
-
-pub use crate::errors::GeoParseError;
-use crate::errors::ParseErrorInternal;
-
-// Two-sided patterns
-lazy_static! {
-    // 40° 26′ 46″ N 79° 58′ 56″ W
-    static ref RE_DMS_NS_DMS_EW: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,2})(?:°\s*|\s+)
-        (\d{1,2})(?:[’'′‘‛]\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[″”"“]?\s*
-        (N|S)
-        \s*
-        [,;]?
-        \s*
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2})(?:[’'′‘‛]\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[″”"“]?\s*
-        (E|W)
-        $
-        "#).unwrap();
-
-    // N 40° 26′ 46″ W 79° 58′ 56″
-    static ref RE_NS_DMS_EW_DMS: Regex = Regex::new(r#"(?x)
-        ^
-        (N|S)\s*
-        (-?\d{1,2})(?:°\s*|\s+)
-        (\d{1,2})(?:[’'′‘‛]\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[″”"“]?
-        \s*
-        [,;]?
-        \s*
-        (E|W)\s*
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2})(?:[’'′‘‛]\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[″”"“]?
-        $
-        "#).unwrap();
-
-    // 40° 26′ 46″ 79° 58′ 56″
-    static ref RE_DMS_DMS: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,2})(?:°\s*|\s+)
-        (\d{1,2})(?:[’'′‘‛]\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[″”"“]?\s*
-        [,;]?
-        \s*
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2})(?:[’'′‘‛]\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[″”"“]?
-        $
-        "#).unwrap();
-
-    // 40° 26.767' N 79° 58.933' W
-    static ref RE_DM_NS_DM_EW: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,2})(?:°\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[’'′‘‛]?\s*
-        (N|S)
-        \s*
-        [,;]?
-        \s*
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[’'′‘‛]?\s*
-        (E|W)
-        $
-        "#).unwrap();
-
-    // N 40° 26.767' W 79° 58.933'
-    static ref RE_NS_DM_EW_DM: Regex = Regex::new(r#"(?x)
-        ^
-        (N|S)\s*
-        (-?\d{1,2})(?:°\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[’'′‘‛]?
-        \s*
-        [,;]?
-        \s*
-        (E|W)\s*
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[’'′‘‛]?
-        $
-        "#).unwrap();
-
-    // 40° 26.767' 79° 58.933'
-    static ref RE_DM_DM: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,2})(?:°\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[’'′‘‛]?
-        \s*
-        [,;]?
-        \s*
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[’'′‘‛]?
-        $
-        "#).unwrap();
-
-    // N 40.446° W 79.982°
-    static ref RE_NS_D_EW_D: Regex = Regex::new(r#"(?x)
-        ^
-        (N|S)\s*
-        (-?\d{1,2}(?:[.,]\d+)?)°?
-        \s*
-        [,;]?
-        \s*
-        (E|W)\s*
-        (-?\d{1,3}(?:[.,]\d+)?)°?
-        $
-        "#).unwrap();
-
-    // 40.446° N 79.982° W
-    static ref RE_D_NS_D_EW: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,2}(?:[.,]\d+)?)°?\s*
-        (N|S)\s*
-        [,;]?
-        \s*
-        (-?\d{1,3}(?:[.,]\d+)?)°?\s*
-        (E|W)
-        $
-        "#).unwrap();
-
-    // 40.446° 79.982°
-    static ref RE_D_D: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,2}(?:[.,]\d+)?)(?:°\s*[,;]?\s*|\s*[,;]\s*|\s+)
-        (-?\d{1,3}(?:[.,]\d+)?)°?
-        $
-        "#).unwrap();
-}
-
-// One-sided patterns
-lazy_static! {
-    // 40° 26′ 46″ N
-    static ref RE_DMS_NSEW: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2})(?:[’'′‘‛]\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[″”"“]?\s*
-        (N|S|E|W)
-        $
-        "#).unwrap();
-
-    // N 40° 26′ 46″
-    static ref RE_NSEW_DMS: Regex = Regex::new(r#"(?x)
-        ^
-        (N|S|E|W)\s*
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2})(?:[’'′‘‛]\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[″”"“]?
-        $
-        "#).unwrap();
-
-    // 40° 26′ 46″
-    static ref RE_DMS: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2})(?:[’'′‘‛]\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[″”"“]?\s*
-        $
-        "#).unwrap();
-
-    // 40° 26.767' N
-    static ref RE_DM_NSEW: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[’'′‘‛]?\s*
-        (N|S|E|W)
-        $
-        "#).unwrap();
-
-    // N 40° 26.767'
-    static ref RE_NSEW_DM: Regex = Regex::new(r#"(?x)
-        ^
-        (N|S|E|W)\s*
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[’'′‘‛]?
-        $
-        "#).unwrap();
-
-    // 40° 26.767'
-    static ref RE_DM: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,3})(?:°\s*|\s+)
-        (\d{1,2}(?:[.,]\d+)?)[’'′‘‛]?
-        $
-        "#).unwrap();
-
-    // N 40.446°
-    static ref RE_NSEW_D: Regex = Regex::new(r#"(?x)
-        ^
-        (N|S|E|W)\s*
-        (-?\d{1,3}(?:[.,]\d+)?)°?
-        $
-        "#).unwrap();
-
-    // 40.446° N
-    static ref RE_D_NSEW: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,3}(?:[.,]\d+)?)°?\s*
-        (N|S|E|W)
-        $
-        "#).unwrap();
-
-    // 40.446°
-    static ref RE_D: Regex = Regex::new(r#"(?x)
-        ^
-        (-?\d{1,3}(?:[.,]\d+)?)°?
-        $
-        "#).unwrap();
-}
-
-/// Parsed degrees, minutes, seconds
-#[derive(Debug, Clone)]
-struct DMS {
-    d: f64,
-    m: f64,
-    s: f64,
-}
-
-// North / South
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum NS {
-    North,
-    South,
-}
-
-impl NS {
-    /// Get the opposite
-    fn invert(self) -> NS {
-        match self {
-            NS::North => NS::South,
-            NS::South => NS::North,
-        }
-    }
-}
-
-// Parsing from string
-impl<'a> TryFrom<&str> for NS {
-    type Error = ParseErrorInternal;
-
-    fn try_from(value: &str) -> Result<Self, ParseErrorInternal> {
-        match value {
-            "N" => Ok(NS::North),
-            "S" => Ok(NS::South),
-            _ => Err(ParseErrorInternal),
-        }
-    }
-}
-
-/// East / West
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum EW {
-    East,
-    West,
-}
-
-impl EW {
-    fn invert(self) -> EW {
-        match self {
-            EW::East => EW::West,
-            EW::West => EW::East,
-        }
-    }
-}
-
-// Parsing from string
-impl<'a> TryFrom<&str> for EW {
-    type Error = ParseErrorInternal;
-
-    fn try_from(value: &str) -> Result<Self, ParseErrorInternal> {
-        match value {
-            "E" => Ok(EW::East),
-            "W" => Ok(EW::West),
-            _ => Err(ParseErrorInternal),
-        }
-    }
-}
+mod fixed;
+mod geodesic;
+mod geohash;
+mod gk;
+mod gpx;
+mod grid;
+mod kml;
+#[cfg(feature = "alloc")]
+mod list;
+mod options;
+mod osgrid;
+mod parser;
+#[cfg(feature = "alloc")]
+mod polyline;
+mod prelude;
+mod relative;
+mod sjtsk;
+mod swiss;
+mod tm;
+mod types;
+mod units;
+mod ups;
+mod utm;
+mod xml;
+
+pub use crate::bbox::{format_bbox, parse_bbox, AxisOrder, BboxDisplay};
+pub use crate::datum::{Datum, Ellipsoid, Helmert};
+pub use crate::errors::{GeoParseError, ItemParseError, OutOfRange, ParseError, ParseErrorKind};
+pub use crate::fixed::{FixedPoint, FixedScale};
+pub use crate::geodesic::{
+    great_circle_destination, great_circle_intermediate, great_circle_midpoint, haversine, Geodesic, GeodesicDirect,
+    GeodesicInverse, EARTH_RADIUS,
+};
+pub use crate::geohash::Geohash;
+pub use crate::gk::{GaussKruger, GaussKrugerSystem};
+#[cfg(feature = "alloc")]
+pub use crate::gpx::write_gpx;
+pub use crate::gpx::{read_gpx, GpxPoints, PathPoint, PointKind};
+#[cfg(feature = "alloc")]
+pub use crate::kml::write_kml;
+pub use crate::kml::{read_kml, KmlPoints};
+#[cfg(feature = "alloc")]
+pub use crate::list::{parse_line_string, parse_many, parse_many_with, parse_polygon};
+pub use crate::options::{LatRangePolicy, LngRangePolicy, ParseOptions, SignPolicy};
+pub use crate::osgrid::{IrishGridRef, OsGridRef};
+#[cfg(feature = "alloc")]
+pub use crate::polyline::{decode_polyline, encode_polyline, PolylinePrecision};
+pub use crate::relative::{parse_relative, DistanceUnit, RelativePosition};
+pub use crate::sjtsk::Sjtsk;
+pub use crate::swiss::{SwissFrame, SwissGrid};
+pub use crate::types::{Dms, Hemisphere, LatLon, Latitude, Longitude};
+pub use crate::units::AngleUnit;
+pub use crate::ups::Ups;
+pub use crate::utm::{Mgrs, Utm};
+use crate::parser::{RawCoord, Sign};
 
 /// Parse a string containing a pair of coordinates (latitude, longitude).
 ///
@@ -307,11 +85,17 @@ impl<'a> TryFrom<&str> for EW {
 /// - `N 40.446° W 79.982°`
 /// - `40.446° N 79.982° W`
 /// - `40.446° 79.982°`, `40.446,79.982`, etc.
+/// - `0.70592 rad, -1.39595 rad`, `44.94g N 88.87g W`, `719.04 mils, -1421.9 mils`
 ///
 /// ## Parser rules
-/// - All formats support negative degrees (preceded by a minus sign).
+/// - All formats support negative degrees (preceded by a minus sign) and an explicit plus sign.
+///   How a sign combines with a hemisphere letter is configured by `ParseOptions::sign`.
 /// - Whitespace is optional and ignored, except for formats that would become unparsable.
 /// - Degree, minute and second symbols can be omitted.
+/// - Degrees, minutes and seconds may all have a decimal part (e.g. `40.5° 30′`, `40° 26′ 46.5″`).
+/// - Comma (`,`) may be used as an alternate decimal separator.
+/// - Decimal formats accept a unit suffix: `°`, `rad`, `gon` / `grad` / `g`, `mil` / `mils` (NATO, 6400 per circle).
+///   Without a suffix, `ParseOptions::unit` is used (degrees by default).
 /// - Unicode quotes (`’`, `”`) may be used in place of apostrophe and double quote (`'`, `"`)
 ///   for minutes and seconds.
 /// - The two coordinates can be separated by comma (`,`), semicolon (`;`), whitespace (` `), or nothing
@@ -319,11 +103,17 @@ impl<'a> TryFrom<&str> for EW {
 ///
 /// # Returns
 /// Returns a `Point` with longitude as X and latitude as Y (natural map orientation), or
-/// a parse error wrapping the source string (for zero-copy patterns)
+/// a parse error wrapping the source string (for zero-copy patterns).
+/// Use `parse_str()` to get the error kind and location instead.
 pub fn parse<T: AsRef<str> + Display>(text: T) -> Result<Point<f64>, GeoParseError<T>> {
-    let s = text.as_ref().trim();
+    parse_with(text, &ParseOptions::default())
+}
 
-    match do_parse(s) {
+/// Parse a string containing a pair of coordinates (latitude, longitude), using custom options.
+///
+/// See `parse()` for supported formats.
+pub fn parse_with<T: AsRef<str> + Display>(text: T, opts: &ParseOptions) -> Result<Point<f64>, GeoParseError<T>> {
+    match parse_str(text.as_ref(), opts) {
         Ok(p) => Ok(p),
         Err(_) => Err(GeoParseError(text)),
     }
@@ -333,9 +123,14 @@ pub fn parse<T: AsRef<str> + Display>(text: T) -> Result<Point<f64>, GeoParseErr
 ///
 /// See `parse()` for supported formats.
 pub fn parse_lat<T: AsRef<str> + Display>(text: T) -> Result<f64, GeoParseError<T>> {
-    let s = text.as_ref().trim();
+    parse_lat_with(text, &ParseOptions::default())
+}
 
-    match do_parse_lat(s) {
+/// Parse string as latitude (N/S), using custom options.
+///
+/// See `parse()` for supported formats.
+pub fn parse_lat_with<T: AsRef<str> + Display>(text: T, opts: &ParseOptions) -> Result<f64, GeoParseError<T>> {
+    match parse_lat_str(text.as_ref(), opts) {
         Ok(p) => Ok(p),
         Err(_) => Err(GeoParseError(text)),
     }
@@ -345,485 +140,114 @@ pub fn parse_lat<T: AsRef<str> + Display>(text: T) -> Result<f64, GeoParseError<
 ///
 /// See `parse()` for supported formats.
 pub fn parse_lng<T: AsRef<str> + Display>(text: T) -> Result<f64, GeoParseError<T>> {
-    let s = text.as_ref().trim();
+    parse_lng_with(text, &ParseOptions::default())
+}
 
-    match do_parse_lng(s) {
+/// Parse string as longitude (E/W), using custom options.
+///
+/// See `parse()` for supported formats.
+pub fn parse_lng_with<T: AsRef<str> + Display>(text: T, opts: &ParseOptions) -> Result<f64, GeoParseError<T>> {
+    match parse_lng_str(text.as_ref(), opts) {
         Ok(p) => Ok(p),
         Err(_) => Err(GeoParseError(text)),
     }
 }
 
-/// Parse to float, treating comma as decimal point (used in some locales)
-trait ParseFloatWithComma {
-    fn parse_allow_comma(self) -> Result<f64, ParseFloatError>;
-}
-
-impl<'a> ParseFloatWithComma for &'a str {
-    fn parse_allow_comma(self) -> Result<f64, ParseFloatError> {
-        if self.contains(',') {
-            let fixed = self.replace(',', ".");
-            fixed.parse()
-        } else {
-            self.parse()
-        }
-    }
-}
-
-/// Validate and compose a complete coordinate (Lat Lng).
-fn build_point(lat: DMS, ns: NS, lng: DMS, ew: EW) -> Result<Point<f64>, ParseErrorInternal> {
-    Ok(Point::new(build_lng(lng, ew)?, build_lat(lat, ns)?))
+/// Parse a string containing a pair of coordinates (latitude, longitude), without allocating.
+///
+/// Unlike `parse_with()`, the error does not hold the input, but tells what went wrong and where.
+/// See `parse()` for supported formats.
+///
+/// ```
+/// use latlon::{parse_str, ParseErrorKind, ParseOptions};
+///
+/// let err = parse_str("40.446° N 79.982° N", &ParseOptions::default()).unwrap_err();
+/// assert_eq!(ParseErrorKind::Hemisphere, err.kind());
+/// assert_eq!(11..21, err.span());
+/// ```
+pub fn parse_str(s: &str, opts: &ParseOptions) -> Result<Point<f64>, ParseError> {
+    parser::parse_pair(s, opts, |lat, lng| build_point(lat, lng, opts))
 }
 
-/// Validate and compose Lng.
-fn build_lng(mut lng: DMS, mut ew: EW) -> Result<f64, ParseErrorInternal> {
-    // the minus sign must go in front of the whole coordinate, not just degrees!
-    if lng.d < 0. {
-        ew = ew.invert();
-        lng.d = -lng.d;
-    }
-
-    let mut lng_f: f64 = lng.d + (lng.m / 60f64) + (lng.s / 3600f64);
-
-    if ew == EW::West {
-        lng_f = -lng_f;
-    }
-
-    if lng_f > 180f64 || lng_f < -180f64 {
-        return Err(ParseErrorInternal);
-    }
-
-    Ok(lng_f)
+/// Parse string as latitude (N/S) without allocating. Positive latitude is North.
+///
+/// See `parse()` for supported formats and `parse_str()` for errors.
+pub fn parse_lat_str(s: &str, opts: &ParseOptions) -> Result<f64, ParseError> {
+    build_lat(parser::parse_single(s, opts)?, opts)
 }
 
-/// Validate and compose Lat
-fn build_lat(mut lat: DMS, mut ns: NS) -> Result<f64, ParseErrorInternal> {
-    // the minus sign must go in front of the whole coordinate, not just degrees!
-    if lat.d < 0. {
-        ns = ns.invert();
-        lat.d = -lat.d;
-    }
-
-    let mut lat_f: f64 = lat.d + (lat.m / 60f64) + (lat.s / 3600f64);
-
-    if ns == NS::South {
-        lat_f = -lat_f;
-    }
-
-    if lat_f > 90f64 || lat_f < -90f64 {
-        return Err(ParseErrorInternal);
-    }
-
-    Ok(lat_f)
+/// Parse string as longitude (E/W) without allocating. Positive longitude is East.
+///
+/// See `parse()` for supported formats and `parse_str()` for errors.
+pub fn parse_lng_str(s: &str, opts: &ParseOptions) -> Result<f64, ParseError> {
+    build_lng(parser::parse_single(s, opts)?, opts)
 }
 
-/// Parse a complete coordinate (Lat Lng).
-/// Patterns are ordered by subjective frequency of use.
-fn do_parse(s: &str) -> Result<Point<f64>, ParseErrorInternal> {
-    if let Some(cap) = RE_D_NS_D_EW.captures(s) {
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        let ns = NS::try_from(cap.get(2).unwrap().as_str())?;
-
-        let lng = DMS {
-            d: cap.get(3).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        let ew = EW::try_from(cap.get(4).unwrap().as_str())?;
-
-        return build_point(lat, ns, lng, ew);
-    }
-
-    if let Some(cap) = RE_NS_DM_EW_DM.captures(s) {
-        let ns = NS::try_from(cap.get(1).unwrap().as_str())?;
-
-        let lat = DMS {
-            d: cap.get(2).unwrap().as_str().parse()?,
-            m: cap.get(3).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        let ew = EW::try_from(cap.get(4).unwrap().as_str())?;
-
-        let lng = DMS {
-            d: cap.get(5).unwrap().as_str().parse()?,
-            m: cap.get(6).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        return build_point(lat, ns, lng, ew);
-    }
-
-    if let Some(cap) = RE_DMS_NS_DMS_EW.captures(s) {
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse()?,
-            s: cap.get(3).unwrap().as_str().parse()?,
-        };
-
-        let ns = NS::try_from(cap.get(4).unwrap().as_str())?;
-
-        let lng = DMS {
-            d: cap.get(5).unwrap().as_str().parse()?,
-            m: cap.get(6).unwrap().as_str().parse()?,
-            s: cap.get(7).unwrap().as_str().parse()?,
-        };
-
-        let ew = EW::try_from(cap.get(8).unwrap().as_str())?;
-
-        return build_point(lat, ns, lng, ew);
-    }
-
-    if let Some(cap) = RE_D_D.captures(s) {
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        let ns = NS::North;
-
-        let lng = DMS {
-            d: cap.get(2).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        let ew = EW::East;
-
-        return build_point(lat, ns, lng, ew);
-    }
-
-    if let Some(cap) = RE_NS_DMS_EW_DMS.captures(s) {
-        let ns = NS::try_from(cap.get(1).unwrap().as_str())?;
-
-        let lat = DMS {
-            d: cap.get(2).unwrap().as_str().parse()?,
-            m: cap.get(3).unwrap().as_str().parse()?,
-            s: cap.get(4).unwrap().as_str().parse()?,
-        };
-
-        let ew = EW::try_from(cap.get(5).unwrap().as_str())?;
-
-        let lng = DMS {
-            d: cap.get(6).unwrap().as_str().parse()?,
-            m: cap.get(7).unwrap().as_str().parse()?,
-            s: cap.get(8).unwrap().as_str().parse()?,
-        };
-
-        return build_point(lat, ns, lng, ew);
-    }
-
-    if let Some(cap) = RE_DMS_DMS.captures(s) {
-        let ns = NS::North;
-        let ew = EW::East;
-
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse()?,
-            s: cap.get(3).unwrap().as_str().parse()?,
-        };
-
-        let lng = DMS {
-            d: cap.get(4).unwrap().as_str().parse()?,
-            m: cap.get(5).unwrap().as_str().parse()?,
-            s: cap.get(6).unwrap().as_str().parse()?,
-        };
-
-        return build_point(lat, ns, lng, ew);
-    }
-
-    if let Some(cap) = RE_DM_NS_DM_EW.captures(s) {
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        let ns = NS::try_from(cap.get(3).unwrap().as_str())?;
-
-        let lng = DMS {
-            d: cap.get(4).unwrap().as_str().parse()?,
-            m: cap.get(5).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        let ew = EW::try_from(cap.get(6).unwrap().as_str())?;
-
-        return build_point(lat, ns, lng, ew);
-    }
-
-    if let Some(cap) = RE_DM_DM.captures(s) {
-        let ns = NS::North;
-        let ew = EW::East;
-
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        let lng = DMS {
-            d: cap.get(3).unwrap().as_str().parse()?,
-            m: cap.get(4).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        return build_point(lat, ns, lng, ew);
-    }
-
-    if let Some(cap) = RE_NS_D_EW_D.captures(s) {
-        let ns = NS::try_from(cap.get(1).unwrap().as_str())?;
-
-        let lat = DMS {
-            d: cap.get(2).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        let ew = EW::try_from(cap.get(3).unwrap().as_str())?;
-
-        let lng = DMS {
-            d: cap.get(4).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        return build_point(lat, ns, lng, ew);
+/// Validate and compose a complete coordinate (Lat Lng).
+fn build_point(lat: RawCoord, lng: RawCoord, opts: &ParseOptions) -> Result<Point<f64>, ParseError> {
+    let point = Point::new(build_lng(lng, opts)?, build_lat(lat, opts)?);
+    Ok(opts.datum.to_wgs84(point))
+}
+
+/// Decide if the coordinate is negative (South or West), combining the sign of degrees
+/// with the hemisphere letter, if any (`Some(true)` for South and West).
+fn resolve_sign(coord: &RawCoord, negative_hemisphere: Option<bool>, policy: SignPolicy) -> Result<bool, ParseError> {
+    let error = ParseError::new(ParseErrorKind::Sign, coord.start, coord.end);
+
+    match (coord.sign, negative_hemisphere) {
+        (sign, None) => Ok(sign == Some(Sign::Minus)),
+        (None, Some(neg)) => Ok(neg),
+        (Some(sign), Some(neg)) => match policy {
+            // the minus sign must go in front of the whole coordinate, not just degrees!
+            SignPolicy::Flip => Ok(neg != (sign == Sign::Minus)),
+            SignPolicy::Reject => Err(error),
+            SignPolicy::Redundant => {
+                if neg == (sign == Sign::Minus) {
+                    Ok(neg)
+                } else {
+                    Err(error)
+                }
+            }
+        },
     }
-
-    Err(ParseErrorInternal)
 }
 
-/// Parse Lat.
-/// Patterns are ordered by subjective frequency of use.
-fn do_parse_lat(s: &str) -> Result<f64, ParseErrorInternal> {
-    if let Some(cap) = RE_D_NSEW.captures(s) {
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        let ns = NS::try_from(cap.get(2).unwrap().as_str())?;
-
-        return build_lat(lat, ns);
-    }
-
-    if let Some(cap) = RE_NSEW_DM.captures(s) {
-        let ns = NS::try_from(cap.get(1).unwrap().as_str())?;
-
-        let lat = DMS {
-            d: cap.get(2).unwrap().as_str().parse()?,
-            m: cap.get(3).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        return build_lat(lat, ns);
-    }
-
-    if let Some(cap) = RE_DMS_NSEW.captures(s) {
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse()?,
-            s: cap.get(3).unwrap().as_str().parse()?,
-        };
-
-        let ns = NS::try_from(cap.get(4).unwrap().as_str())?;
-
-        return build_lat(lat, ns);
-    }
-
-    if let Some(cap) = RE_D.captures(s) {
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        let ns = NS::North;
-
-        return build_lat(lat, ns);
-    }
-
-    if let Some(cap) = RE_NSEW_DMS.captures(s) {
-        let ns = NS::try_from(cap.get(1).unwrap().as_str())?;
-
-        let lat = DMS {
-            d: cap.get(2).unwrap().as_str().parse()?,
-            m: cap.get(3).unwrap().as_str().parse()?,
-            s: cap.get(4).unwrap().as_str().parse()?,
-        };
-
-        return build_lat(lat, ns);
-    }
-
-    if let Some(cap) = RE_DMS.captures(s) {
-        let ns = NS::North;
-
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse()?,
-            s: cap.get(3).unwrap().as_str().parse()?,
-        };
-
-        return build_lat(lat, ns);
-    }
-
-    if let Some(cap) = RE_DM_NSEW.captures(s) {
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        let ns = NS::try_from(cap.get(3).unwrap().as_str())?;
-
-        return build_lat(lat, ns);
-    }
-
-    if let Some(cap) = RE_DM.captures(s) {
-        let ns = NS::North;
-
-        let lat = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        return build_lat(lat, ns);
-    }
+/// Validate and compose Lng.
+fn build_lng(lng: RawCoord, opts: &ParseOptions) -> Result<f64, ParseError> {
+    let error = |kind| ParseError::new(kind, lng.start, lng.end);
 
-    if let Some(cap) = RE_NSEW_D.captures(s) {
-        let ns = NS::try_from(cap.get(1).unwrap().as_str())?;
+    let negative_hemisphere = match lng.hemisphere {
+        None => None,
+        Some(Hemisphere::East) => Some(false),
+        Some(Hemisphere::West) => Some(true),
+        Some(_) => return Err(error(ParseErrorKind::Hemisphere)),
+    };
 
-        let lat = DMS {
-            d: cap.get(2).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
+    let mut lng_f: f64 = lng.to_f64();
 
-        return build_lat(lat, ns);
+    if resolve_sign(&lng, negative_hemisphere, opts.sign)? {
+        lng_f = -lng_f;
     }
 
-    Err(ParseErrorInternal)
+    opts.lng_range.apply(lng_f).ok_or_else(|| error(ParseErrorKind::OutOfRange))
 }
 
-/// Parse Lng.
-/// Patterns are ordered by subjective frequency of use.
-fn do_parse_lng(s: &str) -> Result<f64, ParseErrorInternal> {
-    if let Some(cap) = RE_D_NSEW.captures(s) {
-        let lng = DMS {
-            d: cap.get(1).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        let ew = EW::try_from(cap.get(2).unwrap().as_str())?;
-
-        return build_lng(lng, ew);
-    }
-
-    if let Some(cap) = RE_NSEW_D.captures(s) {
-        let ew = EW::try_from(cap.get(1).unwrap().as_str())?;
-
-        let lng = DMS {
-            d: cap.get(2).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        return build_lng(lng, ew);
-    }
-
-    if let Some(cap) = RE_DMS_NSEW.captures(s) {
-        let lng = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse()?,
-            s: cap.get(3).unwrap().as_str().parse()?,
-        };
-
-        let ew = EW::try_from(cap.get(4).unwrap().as_str())?;
-
-        return build_lng(lng, ew);
-    }
-
-    if let Some(cap) = RE_D.captures(s) {
-        let lng = DMS {
-            d: cap.get(1).unwrap().as_str().parse_allow_comma()?,
-            m: 0.,
-            s: 0.,
-        };
-
-        let ew = EW::East;
-
-        return build_lng(lng, ew);
-    }
-
-    if let Some(cap) = RE_NSEW_DMS.captures(s) {
-        let ew = EW::try_from(cap.get(1).unwrap().as_str())?;
-
-        let lng = DMS {
-            d: cap.get(2).unwrap().as_str().parse()?,
-            m: cap.get(3).unwrap().as_str().parse()?,
-            s: cap.get(4).unwrap().as_str().parse()?,
-        };
-
-        return build_lng(lng, ew);
-    }
-
-    if let Some(cap) = RE_DMS.captures(s) {
-        let ew = EW::East;
-
-        let lng = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse()?,
-            s: cap.get(3).unwrap().as_str().parse()?,
-        };
-
-        return build_lng(lng, ew);
-    }
-
-    if let Some(cap) = RE_DM_NSEW.captures(s) {
-        let lng = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        let ew = EW::try_from(cap.get(3).unwrap().as_str())?;
-
-        return build_lng(lng, ew);
-    }
-
-    if let Some(cap) = RE_NSEW_DM.captures(s) {
-        let ew = EW::try_from(cap.get(1).unwrap().as_str())?;
-
-        let lng = DMS {
-            d: cap.get(2).unwrap().as_str().parse()?,
-            m: cap.get(3).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
-
-        return build_lng(lng, ew);
-    }
+/// Validate and compose Lat
+fn build_lat(lat: RawCoord, opts: &ParseOptions) -> Result<f64, ParseError> {
+    let error = |kind| ParseError::new(kind, lat.start, lat.end);
 
-    if let Some(cap) = RE_DM.captures(s) {
-        let ew = EW::East;
+    let negative_hemisphere = match lat.hemisphere {
+        None => None,
+        Some(Hemisphere::North) => Some(false),
+        Some(Hemisphere::South) => Some(true),
+        Some(_) => return Err(error(ParseErrorKind::Hemisphere)),
+    };
 
-        let lng = DMS {
-            d: cap.get(1).unwrap().as_str().parse()?,
-            m: cap.get(2).unwrap().as_str().parse_allow_comma()?,
-            s: 0.,
-        };
+    let mut lat_f: f64 = lat.to_f64();
 
-        return build_lng(lng, ew);
+    if resolve_sign(&lat, negative_hemisphere, opts.sign)? {
+        lat_f = -lat_f;
     }
 
-    Err(ParseErrorInternal)
+    opts.lat_range.apply(lat_f).ok_or_else(|| error(ParseErrorKind::OutOfRange))
 }
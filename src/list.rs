@@ -0,0 +1,96 @@
+//! Lists of coordinates, e.g. routes and areas pasted one point per line
+
+use crate::{parse_str, ItemParseError, ParseError, ParseErrorKind, ParseOptions, Point};
+use alloc::vec::Vec;
+use geo_types::{LineString, Polygon};
+
+/// Parse a list of coordinate pairs in any of the formats supported by `parse()`.
+///
+/// Points are separated by newlines or semicolons, so within a point, latitude and longitude must be
+/// separated by a comma or whitespace (e.g. `50.08, 14.42; 49.19 16.61`). Empty items (blank lines,
+/// a trailing semicolon) are skipped.
+///
+/// The error tells the index of the item that failed (counting only non-empty items), and its span
+/// is in the whole string.
+///
+/// ```
+/// let points = latlon::parse_many("N 50°5' E 14°25'\n49.19, 16.61; 48.2 16.37").unwrap();
+/// assert_eq!(3, points.len());
+///
+/// let err = latlon::parse_many("50.08, 14.42; 49.19, 16.61; 48.2 N 16.37 N").unwrap_err();
+/// assert_eq!(2, err.index());
+/// ```
+pub fn parse_many(s: &str) -> Result<Vec<Point<f64>>, ItemParseError> {
+    parse_many_with(s, &ParseOptions::default())
+}
+
+/// Parse a list of coordinate pairs, using custom options.
+///
+/// See `parse_many()` for separators.
+pub fn parse_many_with(s: &str, opts: &ParseOptions) -> Result<Vec<Point<f64>>, ItemParseError> {
+    items(s)
+        .enumerate()
+        .map(|(index, (offset, item))| {
+            parse_str(item, opts).map_err(|e| ItemParseError::new(index, e.shifted(offset)))
+        })
+        .collect()
+}
+
+/// Parse a line (e.g. a route) from a list of at least two coordinate pairs, see `parse_many()`.
+///
+/// With fewer points, the error has the index of the first missing item and `ParseErrorKind::Empty`.
+pub fn parse_line_string(s: &str) -> Result<LineString<f64>, ItemParseError> {
+    let points = parse_at_least(s, 2)?;
+    Ok(LineString::from(points))
+}
+
+/// Parse a polygon without holes from a list of at least three distinct coordinate pairs, see `parse_many()`.
+///
+/// The ring is closed automatically, repeating the first point at the end is optional.
+/// With fewer distinct points (not counting the closing one), the error is as in `parse_line_string()`:
+/// its index is past the last item.
+pub fn parse_polygon(s: &str) -> Result<Polygon<f64>, ItemParseError> {
+    let points = parse_many(s)?;
+    let ring = match points.split_last() {
+        Some((last, rest)) if points.len() > 1 && Some(last) == points.first() => rest,
+        _ => &points[..],
+    };
+
+    let mut vertices = Vec::with_capacity(3);
+    for p in ring {
+        if !vertices.contains(p) {
+            vertices.push(*p);
+            if vertices.len() == 3 {
+                return Ok(Polygon::new(LineString::from(points), Vec::new()));
+            }
+        }
+    }
+    Err(too_few(s, points.len()))
+}
+
+/// Parse a list, with the error pointing past the end when there are too few points
+fn parse_at_least(s: &str, min: usize) -> Result<Vec<Point<f64>>, ItemParseError> {
+    let points = parse_many(s)?;
+    if points.len() < min {
+        return Err(too_few(s, points.len()));
+    }
+    Ok(points)
+}
+
+/// Error for a missing item at the end of the list
+fn too_few(s: &str, index: usize) -> ItemParseError {
+    let end = s.trim_end().len();
+    ItemParseError::new(index, ParseError::new(ParseErrorKind::Empty, end, end))
+}
+
+/// Split the list into non-empty trimmed items, with their byte offsets
+fn items(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.split(['\n', ';'])
+        .scan(0, |offset, item| {
+            let start = *offset;
+            *offset += item.len() + 1;
+            let trimmed = item.trim_start();
+            Some((start + item.len() - trimmed.len(), trimmed.trim_end()))
+        })
+        .filter(|(_, item)| !item.is_empty())
+}
@@ -0,0 +1,93 @@
+use crate::{AngleUnit, Datum};
+
+/// How a sign in front of the degrees combines with a hemisphere letter (N, S, E, W).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum SignPolicy {
+    /// A minus sign inverts the hemisphere, e.g. `-40° S` is North. A plus sign is ignored.
+    #[default]
+    Flip,
+    /// A sign combined with a hemisphere letter is an error.
+    Reject,
+    /// The sign must agree with the hemisphere (`-40° S`, `+40° N`), otherwise it is an error.
+    Redundant,
+}
+
+/// What to do with a latitude outside of ±90°
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum LatRangePolicy {
+    /// Out-of-range latitude is an error.
+    #[default]
+    Reject,
+    /// Out-of-range latitude is clamped to the nearest pole.
+    Clamp,
+}
+
+impl LatRangePolicy {
+    /// Apply the policy to a latitude, returning `None` if it is rejected
+    pub(crate) fn apply(self, lat: f64) -> Option<f64> {
+        if (-90f64..=90f64).contains(&lat) {
+            return Some(lat);
+        }
+
+        match self {
+            LatRangePolicy::Reject => None,
+            LatRangePolicy::Clamp => Some(lat.clamp(-90f64, 90f64)),
+        }
+    }
+}
+
+/// What to do with a longitude outside of ±180°
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum LngRangePolicy {
+    /// Out-of-range longitude is an error.
+    #[default]
+    Reject,
+    /// Longitude is wrapped into the range (-180°, 180°], e.g. 280° becomes -80°.
+    /// This also applies to -180°, which becomes 180°.
+    Wrap,
+    /// Longitude is kept as-is, e.g. to preserve the 0–360° convention.
+    Keep,
+}
+
+impl LngRangePolicy {
+    /// Apply the policy to a longitude, returning `None` if it is rejected
+    pub(crate) fn apply(self, lng: f64) -> Option<f64> {
+        match self {
+            LngRangePolicy::Reject => {
+                if (-180f64..=180f64).contains(&lng) {
+                    Some(lng)
+                } else {
+                    None
+                }
+            }
+            LngRangePolicy::Wrap => {
+                if lng > -180f64 && lng <= 180f64 {
+                    Some(lng)
+                } else {
+                    // same as `rem_euclid()`, which is not available without std
+                    let rem = (180f64 - lng) % 360f64;
+                    let rem = if rem < 0f64 { rem + 360f64 } else { rem };
+                    Some(180f64 - rem)
+                }
+            }
+            LngRangePolicy::Keep => Some(lng),
+        }
+    }
+}
+
+/// Parser options
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Handling of degrees that have both a sign and a hemisphere letter
+    pub sign: SignPolicy,
+    /// Handling of latitude outside of ±90°
+    pub lat_range: LatRangePolicy,
+    /// Handling of longitude outside of ±180°
+    pub lng_range: LngRangePolicy,
+    /// Unit of decimal coordinates written without a unit suffix (e.g. `0.70592`).
+    /// Formats with minutes and seconds are always in degrees.
+    pub unit: AngleUnit,
+    /// Datum of the parsed coordinates. Points are converted from it to WGS84.
+    /// Only applies to coordinate pairs, not to a single latitude or longitude.
+    pub datum: Datum,
+}
@@ -0,0 +1,259 @@
+//! British National Grid and Irish Grid references
+
+use crate::datum::{Datum, Ellipsoid};
+use crate::tm::TransverseMercator;
+use crate::{grid, GeoParseError, Point};
+use core::fmt;
+use core::fmt::{Display, Formatter};
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Letters of the 5×5 grid squares, from the north-west corner (`I` is not used)
+const LETTERS: &[u8; 25] = b"ABCDEFGHJKLMNOPQRSTUVWXYZ";
+
+/// Extent of the British National Grid (m)
+const BRITISH_MAX: (f64, f64) = (700_000., 1_300_000.);
+/// Extent of the Irish Grid (m)
+const IRISH_MAX: (f64, f64) = (500_000., 500_000.);
+
+/// Projection of the British National Grid (on OSGB36)
+const BRITISH_TM: TransverseMercator = TransverseMercator {
+    ellipsoid: Ellipsoid::AIRY,
+    k0: 0.9996012717,
+    lng0: -2.,
+    lat0: 49.,
+    false_easting: 400_000.,
+    false_northing: -100_000.,
+};
+
+/// Projection of the Irish Grid (on TM65)
+const IRISH_TM: TransverseMercator = TransverseMercator {
+    ellipsoid: Ellipsoid::AIRY_MODIFIED,
+    k0: 1.000035,
+    lng0: -8.,
+    lat0: 53.5,
+    false_easting: 200_000.,
+    false_northing: 250_000.,
+};
+
+/// Ordnance Survey British National Grid reference, e.g. `TQ 30080 80986`.
+///
+/// `easting` and `northing` are the full numeric coordinates from the false origin south-west of the Scilly Isles.
+///
+/// The `Display` implementation writes the lettered reference to 1 m. Precision (`{:.3}`) sets the number
+/// of digits of easting and northing (0 to 5), e.g. `TQ 300 809` for 100 m. The alternate form (`{:#}`)
+/// writes the numeric coordinates, e.g. `530080 180986`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OsGridRef {
+    /// Easting (m)
+    pub easting: f64,
+    /// Northing (m)
+    pub northing: f64,
+}
+
+impl OsGridRef {
+    /// Create from numeric easting and northing (no range checking is done)
+    pub fn new(easting: f64, northing: f64) -> OsGridRef {
+        OsGridRef { easting, northing }
+    }
+
+    /// Project a WGS84 point. Returns `None` outside the grid.
+    pub fn from_point(point: Point<f64>) -> Option<OsGridRef> {
+        let (easting, northing) = project(point, Datum::Osgb36, &BRITISH_TM, BRITISH_MAX)?;
+        Some(OsGridRef { easting, northing })
+    }
+
+    /// Get the WGS84 point
+    pub fn to_point(&self) -> Point<f64> {
+        unproject(self.easting, self.northing, Datum::Osgb36, &BRITISH_TM)
+    }
+
+    /// Parse a grid reference.
+    ///
+    /// Lettered references with any even number of digits up to 10 are accepted, with or without
+    /// spaces (`TQ 30080 80986`, `SU387148`, `NN16`, `TQ`), as well as numeric easting and northing
+    /// (`530080, 180986`). A lettered reference gives the south-west corner of its square.
+    pub fn parse<T: AsRef<str> + Display>(text: T) -> Result<OsGridRef, GeoParseError<T>> {
+        match parse_grid_ref(text.as_ref(), 2, BRITISH_MAX) {
+            Some((easting, northing)) => Ok(OsGridRef { easting, northing }),
+            None => Err(GeoParseError(text)),
+        }
+    }
+}
+
+impl From<OsGridRef> for Point<f64> {
+    fn from(r: OsGridRef) -> Self {
+        r.to_point()
+    }
+}
+
+impl Display for OsGridRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        format_grid_ref(f, self.easting, self.northing, 2)
+    }
+}
+
+/// Irish Grid reference, e.g. `O 15814 34256`.
+///
+/// `easting` and `northing` are the full numeric coordinates, and the reference is written as `OsGridRef`,
+/// with a single letter of the 100 km square.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IrishGridRef {
+    /// Easting (m)
+    pub easting: f64,
+    /// Northing (m)
+    pub northing: f64,
+}
+
+impl IrishGridRef {
+    /// Create from numeric easting and northing (no range checking is done)
+    pub fn new(easting: f64, northing: f64) -> IrishGridRef {
+        IrishGridRef { easting, northing }
+    }
+
+    /// Project a WGS84 point. Returns `None` outside the grid.
+    pub fn from_point(point: Point<f64>) -> Option<IrishGridRef> {
+        let (easting, northing) = project(point, Datum::Tm65, &IRISH_TM, IRISH_MAX)?;
+        Some(IrishGridRef { easting, northing })
+    }
+
+    /// Get the WGS84 point
+    pub fn to_point(&self) -> Point<f64> {
+        unproject(self.easting, self.northing, Datum::Tm65, &IRISH_TM)
+    }
+
+    /// Parse a grid reference, lettered (`O 15814 34256`, `O158342`) or numeric (`315814, 234256`).
+    ///
+    /// See `OsGridRef::parse()`.
+    pub fn parse<T: AsRef<str> + Display>(text: T) -> Result<IrishGridRef, GeoParseError<T>> {
+        match parse_grid_ref(text.as_ref(), 1, IRISH_MAX) {
+            Some((easting, northing)) => Ok(IrishGridRef { easting, northing }),
+            None => Err(GeoParseError(text)),
+        }
+    }
+}
+
+impl From<IrishGridRef> for Point<f64> {
+    fn from(r: IrishGridRef) -> Self {
+        r.to_point()
+    }
+}
+
+impl Display for IrishGridRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        format_grid_ref(f, self.easting, self.northing, 1)
+    }
+}
+
+fn project(point: Point<f64>, datum: Datum, tm: &TransverseMercator, max: (f64, f64)) -> Option<(f64, f64)> {
+    if !(-90f64..=90f64).contains(&point.y()) || !(-180f64..=180f64).contains(&point.x()) {
+        return None;
+    }
+
+    let local = Datum::Wgs84.transform(point, datum);
+    let (easting, northing) = tm.forward(local.y(), local.x());
+    in_grid(easting, northing, max).then_some((easting, northing))
+}
+
+fn unproject(easting: f64, northing: f64, datum: Datum, tm: &TransverseMercator) -> Point<f64> {
+    let (lat, lng) = tm.inverse(easting, northing);
+    datum.to_wgs84(Point::new(lng, lat))
+}
+
+fn in_grid(easting: f64, northing: f64, max: (f64, f64)) -> bool {
+    (0. ..max.0).contains(&easting) && (0. ..max.1).contains(&northing)
+}
+
+/// Get the easting and northing (in 100 km) of the square of the letters
+/// (two letters of the British grid, or one of the Irish grid)
+fn square_offset(letters: &[u8]) -> Option<(i32, i32)> {
+    let mut offset = (0, 0);
+    for (i, letter) in letters.iter().enumerate() {
+        let index = LETTERS.iter().position(|l| l.eq_ignore_ascii_case(letter))? as i32;
+        // the first letter of the British grid is a 500 km square, with `S` at the false origin
+        let (size, origin) = if letters.len() == 2 && i == 0 { (5, (2, 1)) } else { (1, (0, 0)) };
+        offset.0 += size * (index % 5 - origin.0);
+        offset.1 += size * (4 - index / 5 - origin.1);
+    }
+    Some(offset)
+}
+
+/// Get the letters of the 100 km square
+fn square_letters(easting: u32, northing: u32, count: usize) -> [u8; 2] {
+    let (e, n) = (easting / 100_000, northing / 100_000);
+    let letter = |col: u32, row: u32| LETTERS[((4 - row.min(4)) * 5 + col.min(4)) as usize];
+    if count == 2 {
+        [letter(e / 5 + 2, n / 5 + 1), letter(e % 5, n % 5)]
+    } else {
+        [letter(e, n), 0]
+    }
+}
+
+fn parse_grid_ref(s: &str, letter_count: usize, max: (f64, f64)) -> Option<(f64, f64)> {
+    let s = s.trim();
+    if !s.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        let (easting, northing) = grid::parse_pair(s)?;
+        return in_grid(easting, northing, max).then_some((easting, northing));
+    }
+
+    let letters = s.get(..letter_count)?;
+    if !letters.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    let (e100k, n100k) = square_offset(letters.as_bytes())?;
+
+    // digits as one run, or two runs of the same length
+    let rest = &s[letter_count..];
+    if !rest.chars().all(|c| c.is_ascii_digit() || c.is_whitespace()) {
+        return None;
+    }
+    let mut parts = rest.split_whitespace();
+    let (easting, northing) = match (parts.next(), parts.next(), parts.next()) {
+        (None, _, _) => ("", ""),
+        (Some(digits), None, _) if digits.len() % 2 == 0 => digits.split_at(digits.len() / 2),
+        (Some(easting), Some(northing), None) if easting.len() == northing.len() => (easting, northing),
+        _ => return None,
+    };
+
+    let digits = easting.len();
+    if digits > 5 {
+        return None;
+    }
+
+    let value = |part: &str| -> f64 {
+        let n = part.parse::<u32>().unwrap_or(0);
+        f64::from(n * 10u32.pow((5 - digits) as u32))
+    };
+    let easting = f64::from(e100k) * 100_000. + value(easting);
+    let northing = f64::from(n100k) * 100_000. + value(northing);
+
+    in_grid(easting, northing, max).then_some((easting, northing))
+}
+
+fn format_grid_ref(f: &mut Formatter<'_>, easting: f64, northing: f64, letter_count: usize) -> fmt::Result {
+    if f.alternate() {
+        let precision = f.precision().unwrap_or(0);
+        return write!(f, "{:.*} {:.*}", precision, easting, precision, northing);
+    }
+
+    // grid references truncate the coordinates to the reference precision
+    let easting = easting.floor().max(0.) as u32;
+    let northing = northing.floor().max(0.) as u32;
+    let letters = square_letters(easting, northing, letter_count);
+    for &letter in &letters[..letter_count] {
+        write!(f, "{}", char::from(letter))?;
+    }
+
+    let digits = f.precision().unwrap_or(5).min(5);
+    if digits > 0 {
+        let divisor = 10u32.pow(5 - digits as u32);
+        write!(
+            f,
+            " {:0w$} {:0w$}",
+            easting % 100_000 / divisor,
+            northing % 100_000 / divisor,
+            w = digits
+        )?;
+    }
+    Ok(())
+}
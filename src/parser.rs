@@ -0,0 +1,591 @@
+//! Single-pass tokenizer and parser of the supported coordinate formats.
+//!
+//! The input is first split into tokens (numbers, symbols, hemisphere letters, separators).
+//! The shape of the token sequence then decides the format, without trying patterns one by one.
+
+use crate::errors::{ParseError, ParseErrorKind};
+use crate::{AngleUnit, Hemisphere, ParseOptions};
+
+/// Maximum number of tokens in a valid input, e.g. `N 40° 26′ 46″, W 79° 58′ 56″` has 15.
+const MAX_TOKENS: usize = 16;
+
+/// Maximum integer digits of degrees in the DMS and DM formats (latitude in a pair)
+const PAIR_LAT_DEG_DIGITS: usize = 2;
+/// Maximum integer digits of degrees in the DMS and DM formats (longitude in a pair, or alone)
+const DEG_DIGITS: usize = 3;
+/// Maximum integer digits of degrees in the decimal formats (also used for other units)
+const DECIMAL_DIGITS: usize = 4;
+/// Maximum integer digits of minutes and seconds
+const MIN_SEC_DIGITS: usize = 2;
+/// Maximum length of a number with decimal comma, which is copied to a stack buffer to be parsed
+const COMMA_NUMBER_LEN: usize = 64;
+
+/// Explicit sign of the degrees
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum Sign {
+    Plus,
+    Minus,
+}
+
+/// Parsed degrees, minutes and seconds of one coordinate
+#[derive(Debug, Clone)]
+pub(crate) struct RawCoord {
+    /// Sign written in front of the degrees, if any
+    pub sign: Option<Sign>,
+    /// Degrees (unsigned)
+    pub d: f64,
+    pub m: f64,
+    pub s: f64,
+    /// Hemisphere letter, if any
+    pub hemisphere: Option<Hemisphere>,
+    /// Byte range in the input
+    pub start: usize,
+    pub end: usize,
+}
+
+impl RawCoord {
+    /// Get the unsigned decimal value
+    pub fn to_f64(&self) -> f64 {
+        self.d + (self.m / 60f64) + (self.s / 3600f64)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    /// Number with an optional sign and decimal part
+    Num,
+    /// `°`
+    Deg,
+    /// Minutes symbol
+    Min,
+    /// Seconds symbol
+    Sec,
+    /// Unit suffix (`rad`, `gon`, ...)
+    Unit(AngleUnit),
+    /// `N`, `S`, `E`, `W`
+    Letter(Hemisphere),
+    /// `,` or `;`
+    Sep,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: Kind,
+    /// Token is preceded by whitespace
+    ws: bool,
+    /// Byte range in the input
+    start: usize,
+    end: usize,
+    /// Sign of a number
+    sign: Option<Sign>,
+    /// Number of integer digits of a number
+    int_digits: usize,
+}
+
+impl Token {
+    const EMPTY: Token = Token {
+        kind: Kind::Sep,
+        ws: false,
+        start: 0,
+        end: 0,
+        sign: None,
+        int_digits: 0,
+    };
+
+    fn is_num(&self) -> bool {
+        self.kind == Kind::Num
+    }
+
+    fn is_letter(&self) -> bool {
+        matches!(self.kind, Kind::Letter(_))
+    }
+
+    /// Check if this is an unsigned number
+    fn is_unsigned_num(&self) -> bool {
+        self.kind == Kind::Num && self.sign.is_none()
+    }
+
+    /// Check if this is the given symbol, written directly after the previous token
+    fn is_attached(&self, kind: Kind) -> bool {
+        self.kind == kind && !self.ws
+    }
+
+    /// Get the digits of a number (without sign)
+    fn digits<'a>(&self, s: &'a str) -> &'a str {
+        let start = if self.sign.is_some() { self.start + 1 } else { self.start };
+        &s[start..self.end]
+    }
+
+    /// Get the position of decimal comma in a number
+    fn comma(&self, s: &str) -> Option<usize> {
+        let pos = if self.sign.is_some() { self.start + 1 } else { self.start } + self.int_digits;
+        if pos < self.end && s.as_bytes()[pos] == b',' {
+            Some(pos)
+        } else {
+            None
+        }
+    }
+}
+
+/// Token buffer, allocated on stack
+struct Tokens {
+    buf: [Token; MAX_TOKENS],
+    len: usize,
+}
+
+impl Tokens {
+    fn push(&mut self, token: Token) -> Result<(), ParseError> {
+        if self.len == MAX_TOKENS {
+            return Err(ParseError::new(ParseErrorKind::TooLong, token.start, token.end));
+        }
+        self.buf[self.len] = token;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn as_slice(&self) -> &[Token] {
+        &self.buf[..self.len]
+    }
+
+    /// Get an error spanning all tokens
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        match self.as_slice() {
+            [] => ParseError::new(ParseErrorKind::Empty, 0, 0),
+            [first, .., last] => ParseError::new(kind, first.start, last.end),
+            [t] => ParseError::new(kind, t.start, t.end),
+        }
+    }
+}
+
+/// Split the input to tokens
+fn tokenize(s: &str) -> Result<Tokens, ParseError> {
+    let bytes = s.as_bytes();
+    let mut tokens = Tokens {
+        buf: [Token::EMPTY; MAX_TOKENS],
+        len: 0,
+    };
+
+    let mut ws = false;
+    let mut i = 0;
+    while let Some(c) = s[i..].chars().next() {
+        let mut token = Token {
+            ws,
+            start: i,
+            end: i + c.len_utf8(),
+            ..Token::EMPTY
+        };
+        let start = i;
+        let error = move |kind, end| ParseError::new(kind, start, end);
+
+        match c {
+            c if c.is_whitespace() => {
+                ws = true;
+                i = token.end;
+                continue;
+            }
+            '+' | '-' | '0'..='9' => {
+                let mut j = i;
+                if c == '+' || c == '-' {
+                    token.sign = Some(if c == '-' { Sign::Minus } else { Sign::Plus });
+                    j += 1;
+                }
+
+                let digits_start = j;
+                while j < bytes.len() && bytes[j].is_ascii_digit() {
+                    j += 1;
+                }
+
+                token.int_digits = j - digits_start;
+                if token.int_digits == 0 {
+                    return Err(error(ParseErrorKind::InvalidNumber, token.end));
+                }
+
+                // decimal part, comma is also accepted as decimal separator
+                if j + 1 < bytes.len() && (bytes[j] == b'.' || bytes[j] == b',') && bytes[j + 1].is_ascii_digit() {
+                    let int_end = j;
+                    j += 1;
+                    while j < bytes.len() && bytes[j].is_ascii_digit() {
+                        j += 1;
+                    }
+
+                    // in `40,79.5` the comma can only be a separator
+                    if bytes[int_end] == b',' && j + 1 < bytes.len() && bytes[j] == b'.' && bytes[j + 1].is_ascii_digit() {
+                        j = int_end;
+                    }
+                }
+
+                token.kind = Kind::Num;
+                token.end = j;
+            }
+            '°' => token.kind = Kind::Deg,
+            '’' | '\'' | '′' | '‘' | '‛' => token.kind = Kind::Min,
+            '″' | '”' | '"' | '“' => token.kind = Kind::Sec,
+            ',' | ';' => token.kind = Kind::Sep,
+            'N' => token.kind = Kind::Letter(Hemisphere::North),
+            'S' => token.kind = Kind::Letter(Hemisphere::South),
+            'E' => token.kind = Kind::Letter(Hemisphere::East),
+            'W' => token.kind = Kind::Letter(Hemisphere::West),
+            'a'..='z' => {
+                let mut j = i;
+                while j < bytes.len() && bytes[j].is_ascii_lowercase() {
+                    j += 1;
+                }
+
+                let unit = AngleUnit::from_suffix(&s[i..j]).ok_or_else(|| error(ParseErrorKind::UnknownUnit, j))?;
+                token.kind = Kind::Unit(unit);
+                token.end = j;
+            }
+            _ => return Err(error(ParseErrorKind::UnexpectedChar, token.end)),
+        }
+
+        i = token.end;
+        ws = false;
+        tokens.push(token)?;
+    }
+
+    Ok(tokens)
+}
+
+/// Numbers making up one coordinate
+#[derive(Debug, Clone, Copy)]
+enum Value {
+    /// Decimal, with an optional unit
+    Decimal(Token, Option<AngleUnit>),
+    /// Degrees and decimal minutes
+    DegMin(Token, Token),
+    /// Degrees, minutes and decimal seconds
+    DegMinSec(Token, Token, Token),
+}
+
+impl Value {
+    /// Check that the two values use the same format
+    fn same_format(&self, other: &Value) -> bool {
+        core::mem::discriminant(self) == core::mem::discriminant(other)
+    }
+
+    /// Check the number of integer digits of each component
+    fn check_digits(&self, deg_digits: usize) -> Option<()> {
+        let ok = match self {
+            Value::Decimal(d, _) => d.int_digits <= DECIMAL_DIGITS,
+            Value::DegMin(d, m) => d.int_digits <= deg_digits && m.int_digits <= MIN_SEC_DIGITS,
+            Value::DegMinSec(d, m, s) => {
+                d.int_digits <= deg_digits && m.int_digits <= MIN_SEC_DIGITS && s.int_digits <= MIN_SEC_DIGITS
+            }
+        };
+
+        if ok {
+            Some(())
+        } else {
+            None
+        }
+    }
+}
+
+/// Match tokens of a single coordinate without hemisphere letters. All tokens must be used.
+fn value(toks: &[Token]) -> Option<Value> {
+    let (d, rest) = toks.split_first()?;
+    if !d.is_num() {
+        return None;
+    }
+
+    // decimal
+    match rest {
+        [] => return Some(Value::Decimal(*d, None)),
+        [u] => match u.kind {
+            Kind::Deg => return Some(Value::Decimal(*d, Some(AngleUnit::Degrees))),
+            Kind::Unit(unit) => return Some(Value::Decimal(*d, Some(unit))),
+            _ => {}
+        },
+        _ => {}
+    }
+
+    // minutes must be separated from degrees by the degree symbol or whitespace
+    let (marked, rest) = match rest.split_first()? {
+        (t, rest) if t.is_attached(Kind::Deg) => (true, rest),
+        _ => (false, rest),
+    };
+
+    let (m, rest) = rest.split_first()?;
+    if !m.is_unsigned_num() || !(marked || m.ws) {
+        return None;
+    }
+
+    match rest {
+        [] => return Some(Value::DegMin(*d, *m)),
+        [t] if t.is_attached(Kind::Min) => return Some(Value::DegMin(*d, *m)),
+        _ => {}
+    }
+
+    // seconds must be separated from minutes by the minutes symbol or whitespace
+    let (marked, rest) = match rest.split_first()? {
+        (t, rest) if t.is_attached(Kind::Min) => (true, rest),
+        _ => (false, rest),
+    };
+
+    let (s, rest) = rest.split_first()?;
+    if !s.is_unsigned_num() || !(marked || s.ws) {
+        return None;
+    }
+
+    match rest {
+        [] => Some(Value::DegMinSec(*d, *m, *s)),
+        [t] if t.is_attached(Kind::Sec) => Some(Value::DegMinSec(*d, *m, *s)),
+        _ => None,
+    }
+}
+
+/// One coordinate matched in the input
+#[derive(Debug, Clone, Copy)]
+struct Side {
+    value: Value,
+    hemisphere: Option<Hemisphere>,
+    /// Byte range in the input
+    start: usize,
+    end: usize,
+}
+
+/// Match tokens of a single coordinate with an optional hemisphere letter before or after the numbers
+fn side(toks: &[Token]) -> Option<Side> {
+    let (value, hemisphere) = match toks {
+        [Token { kind: Kind::Letter(h), .. }, rest @ ..] => (value(rest)?, Some(*h)),
+        [rest @ .., Token { kind: Kind::Letter(h), .. }] => (value(rest)?, Some(*h)),
+        _ => (value(toks)?, None),
+    };
+
+    Some(Side {
+        value,
+        hemisphere,
+        start: toks.first()?.start,
+        end: toks.last()?.end,
+    })
+}
+
+/// Strip one separator from the end
+fn strip_sep(toks: &[Token]) -> &[Token] {
+    match toks {
+        [rest @ .., t] if t.kind == Kind::Sep => rest,
+        _ => toks,
+    }
+}
+
+/// Strip one separator from the start
+fn strip_sep_start(toks: &[Token]) -> &[Token] {
+    match toks {
+        [t, rest @ ..] if t.kind == Kind::Sep => rest,
+        _ => toks,
+    }
+}
+
+/// Match tokens of a coordinate pair (latitude, longitude).
+///
+/// Hemisphere letters are not checked against the axes here, so that a wrong letter
+/// can be reported as such.
+fn pair(toks: &[Token]) -> Option<(Side, Side)> {
+    let first = toks.first()?;
+    let last = toks.last()?;
+
+    let (lat, lng) = if first.is_letter() {
+        // N 40° 26′ 46″ W 79° 58′ 56″
+        let k = 1 + toks[1..].iter().position(Token::is_letter)?;
+        (strip_sep(&toks[..k]), &toks[k..])
+    } else if last.is_letter() {
+        // 40° 26′ 46″ N 79° 58′ 56″ W
+        let k = toks.iter().position(Token::is_letter)?;
+        (&toks[..=k], strip_sep_start(&toks[k + 1..]))
+    } else if toks.iter().any(Token::is_letter) {
+        // letters must be either before or after both coordinates
+        return None;
+    } else if let Some(k) = toks.iter().position(|t| t.kind == Kind::Sep) {
+        // 40° 26′ 46″, 79° 58′ 56″
+        (&toks[..k], &toks[k + 1..])
+    } else {
+        // 40° 26′ 46″ 79° 58′ 56″ - split in half by count of numbers
+        let count = toks.iter().filter(|t| t.is_num()).count();
+        if count % 2 != 0 {
+            return None;
+        }
+
+        let k = toks.iter().enumerate().filter(|(_, t)| t.is_num()).nth(count / 2)?.0;
+
+        // decimal degrees must be separated by a unit or whitespace
+        if count == 2 && k == 1 && !toks[k].ws {
+            return None;
+        }
+
+        (&toks[..k], &toks[k..])
+    };
+
+    let lat = side(lat)?;
+    let lng = side(lng)?;
+
+    if !lat.value.same_format(&lng.value) {
+        return None;
+    }
+
+    lat.value.check_digits(PAIR_LAT_DEG_DIGITS)?;
+    lng.value.check_digits(DEG_DIGITS)?;
+
+    Some((lat, lng))
+}
+
+/// Convert a matched coordinate to numbers
+fn raw_coord(s: &str, side: Side, opts: &ParseOptions) -> Result<RawCoord, ParseError> {
+    let num = |t: Token| parse_number(s, t);
+
+    let (d, m, sec) = match side.value {
+        Value::Decimal(d, unit) => (unit.unwrap_or(opts.unit).to_degrees(num(d)?), 0., 0.),
+        Value::DegMin(d, m) => (num(d)?, num(m)?, 0.),
+        Value::DegMinSec(d, m, sec) => (num(d)?, num(m)?, num(sec)?),
+    };
+
+    let sign = match side.value {
+        Value::Decimal(d, _) | Value::DegMin(d, _) | Value::DegMinSec(d, _, _) => d.sign,
+    };
+
+    Ok(RawCoord {
+        sign,
+        d,
+        m,
+        s: sec,
+        hemisphere: side.hemisphere,
+        start: side.start,
+        end: side.end,
+    })
+}
+
+/// Parse the digits of a number token. Decimal comma (used in some locales) is replaced
+/// by a point in a copy on stack.
+fn parse_number(s: &str, t: Token) -> Result<f64, ParseError> {
+    let error = ParseError::new(ParseErrorKind::InvalidNumber, t.start, t.end);
+    let digits = t.digits(s);
+
+    if t.comma(s).is_none() {
+        return digits.parse().map_err(|_| error);
+    }
+
+    let mut buf = [0u8; COMMA_NUMBER_LEN];
+    let buf = buf.get_mut(..digits.len()).ok_or(error)?;
+    for (b, c) in buf.iter_mut().zip(digits.bytes()) {
+        *b = if c == b',' { b'.' } else { c };
+    }
+
+    core::str::from_utf8(buf).ok().and_then(|d| d.parse().ok()).ok_or(error)
+}
+
+/// Parse a single coordinate (latitude or longitude)
+pub(crate) fn parse_single(s: &str, opts: &ParseOptions) -> Result<RawCoord, ParseError> {
+    let tokens = tokenize(s)?;
+    let side = side(tokens.as_slice()).ok_or_else(|| tokens.error(ParseErrorKind::Format))?;
+    side.value.check_digits(DEG_DIGITS).ok_or_else(|| tokens.error(ParseErrorKind::Format))?;
+    raw_coord(s, side, opts)
+}
+
+/// Parse a coordinate pair (latitude, longitude) and build the result. If a reading of the decimal commas
+/// gives an invalid coordinate, the other readings are tried.
+pub(crate) fn parse_pair<T>(
+    s: &str,
+    opts: &ParseOptions,
+    build: impl Fn(RawCoord, RawCoord) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    let tokens = tokenize(s)?;
+
+    // error of the first reading that matched a format, returned if no other one is valid
+    let mut error = None;
+    let mut attempt = |toks: &[Token]| -> Option<T> {
+        let (lat, lng) = pair(toks)?;
+        let result = raw_coord(s, lat, opts).and_then(|lat| build(lat, raw_coord(s, lng, opts)?));
+        match result {
+            Ok(v) => Some(v),
+            Err(e) => {
+                error.get_or_insert(e);
+                None
+            }
+        }
+    };
+
+    if let Some(v) = attempt(tokens.as_slice()) {
+        return Ok(v);
+    }
+
+    // A comma between digits is taken as a decimal separator, but it may as well separate
+    // latitude and longitude (e.g. `40,79`). Try splitting numbers at the comma, last first.
+    // If that fails, the number after the split may continue with a decimal comma, which the
+    // tokenizer took as a separator (e.g. `50,14,4215` is `50` and `14,4215`).
+    for &join in [false, true].iter() {
+        for i in (0..tokens.len).rev() {
+            let t = tokens.buf[i];
+            let comma = match t.comma(s) {
+                Some(pos) if t.is_num() && tokens.len + 2 <= MAX_TOKENS => pos,
+                _ => continue,
+            };
+
+            // a separator comma and an integer, attached to the number
+            let decimal = match tokens.as_slice().get(i + 1..i + 3) {
+                Some([sep, next])
+                    if sep.is_attached(Kind::Sep)
+                        && &s[sep.start..sep.end] == ","
+                        && next.is_unsigned_num()
+                        && !next.ws
+                        && next.int_digits == next.end - next.start =>
+                {
+                    Some(*next)
+                }
+                _ => None,
+            };
+            if join && decimal.is_none() {
+                continue;
+            }
+
+            let mut split = Tokens {
+                buf: [Token::EMPTY; MAX_TOKENS],
+                len: 0,
+            };
+
+            let mut skip = 0;
+            for (j, t) in tokens.as_slice().iter().enumerate() {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                if j != i {
+                    split.push(*t)?;
+                    continue;
+                }
+
+                split.push(Token {
+                    end: comma,
+                    ..*t
+                })?;
+                split.push(Token {
+                    kind: Kind::Sep,
+                    ws: false,
+                    start: comma,
+                    end: comma + 1,
+                    ..Token::EMPTY
+                })?;
+
+                let end = match decimal {
+                    Some(next) if join => {
+                        skip = 2;
+                        next.end
+                    }
+                    _ => t.end,
+                };
+                split.push(Token {
+                    kind: Kind::Num,
+                    ws: false,
+                    start: comma + 1,
+                    end,
+                    sign: None,
+                    int_digits: t.end - comma - 1,
+                })?;
+            }
+
+            if let Some(v) = attempt(split.as_slice()) {
+                return Ok(v);
+            }
+        }
+    }
+
+    Err(error.unwrap_or_else(|| tokens.error(ParseErrorKind::Format)))
+}
@@ -0,0 +1,159 @@
+//! Google encoded polyline algorithm format, e.g. `_p~iF~ps|U_ulLnnqC_mqNvxq`@`
+
+use crate::{OutOfRange, ParseError, ParseErrorKind, Point};
+use alloc::string::String;
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Longest encoded value: 7 chunks of 5 bits hold any coordinate difference at `E6`
+const MAX_CHUNKS: usize = 7;
+
+/// Precision of an encoded polyline, i.e. the size of one integer unit
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum PolylinePrecision {
+    /// 1e-5 degree, used by the Google Maps APIs
+    #[default]
+    E5,
+    /// 1e-6 degree, used e.g. by OSRM and Valhalla
+    E6,
+}
+
+impl PolylinePrecision {
+    /// Number of units in one degree
+    pub fn units_per_degree(self) -> f64 {
+        match self {
+            PolylinePrecision::E5 => 100_000.,
+            PolylinePrecision::E6 => 1_000_000.,
+        }
+    }
+}
+
+/// Decode a polyline into points (use `LineString::from()` to get a line).
+///
+/// An empty string gives no points. The error points at the offending character, or at the last
+/// value if it is incomplete, too long or out of range.
+///
+/// ```
+/// use latlon::{decode_polyline, PolylinePrecision};
+///
+/// let points = decode_polyline("_p~iF~ps|U_ulLnnqC_mqNvxq`@", PolylinePrecision::E5).unwrap();
+/// assert_eq!(vec![(-120.2, 38.5), (-120.95, 40.7), (-126.453, 43.252)],
+///     points.iter().map(|p| p.x_y()).collect::<Vec<_>>());
+/// ```
+pub fn decode_polyline(s: &str, precision: PolylinePrecision) -> Result<Vec<Point<f64>>, ParseError> {
+    let unit = precision.units_per_degree();
+    let mut points = Vec::new();
+    let (mut lat, mut lng) = (0i64, 0i64);
+
+    let mut values = Values { s, pos: 0 };
+    while values.pos < s.len() {
+        let start = values.pos;
+        let d_lat = values.next_value()?;
+        let d_lng = match values.next_value() {
+            Ok(v) => v,
+            Err(e) if e.kind() == ParseErrorKind::Empty => {
+                return Err(ParseError::new(ParseErrorKind::InvalidNumber, start, s.len()));
+            }
+            Err(e) => return Err(e),
+        };
+
+        // the values are bounded and the previous point is in range, so this cannot overflow
+        lat += d_lat;
+        lng += d_lng;
+        let point = Point::new(lng as f64 / unit, lat as f64 / unit);
+        if point.y().abs() > 90. || point.x().abs() > 180. {
+            return Err(ParseError::new(ParseErrorKind::OutOfRange, start, values.pos));
+        }
+        points.push(point);
+    }
+
+    Ok(points)
+}
+
+/// Encode points as a polyline (use `LineString::points()` to encode a line).
+///
+/// Coordinates are rounded to the precision, and the rounding errors do not accumulate.
+/// Returns `OutOfRange` if a latitude is not within ±90° or a longitude within ±180° (or not finite).
+///
+/// ```
+/// use latlon::{encode_polyline, Point, PolylinePrecision};
+///
+/// let points = [Point::new(-120.2, 38.5), Point::new(-120.95, 40.7), Point::new(-126.453, 43.252)];
+/// assert_eq!("_p~iF~ps|U_ulLnnqC_mqNvxq`@", encode_polyline(points.iter().copied(), PolylinePrecision::E5).unwrap());
+/// ```
+pub fn encode_polyline<I: IntoIterator<Item = Point<f64>>>(
+    points: I,
+    precision: PolylinePrecision,
+) -> Result<String, OutOfRange> {
+    let unit = precision.units_per_degree();
+    let mut out = String::new();
+    let (mut lat, mut lng) = (0i64, 0i64);
+
+    for point in points {
+        // written to also reject NaN
+        if !(point.y().abs() <= 90. && point.x().abs() <= 180.) {
+            return Err(OutOfRange);
+        }
+        let (next_lat, next_lng) = ((point.y() * unit).round() as i64, (point.x() * unit).round() as i64);
+        push_value(&mut out, next_lat - lat);
+        push_value(&mut out, next_lng - lng);
+        lat = next_lat;
+        lng = next_lng;
+    }
+
+    Ok(out)
+}
+
+/// Append a signed value as 5-bit chunks, least significant first
+fn push_value(out: &mut String, value: i64) {
+    // the sign goes to the lowest bit, negative values are inverted
+    let mut v = (if value < 0 { !(value << 1) } else { value << 1 }) as u64;
+    while v >= 0x20 {
+        out.push(char::from((0x20 | (v & 0x1f)) as u8 + 63));
+        v >>= 5;
+    }
+    out.push(char::from(v as u8 + 63));
+}
+
+/// Reader of the encoded values
+struct Values<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl Values<'_> {
+    /// Read the next value, the error kind is `Empty` at the end of the string
+    fn next_value(&mut self) -> Result<i64, ParseError> {
+        let start = self.pos;
+        let mut result = 0u64;
+        let mut shift = 0;
+        let mut chunks = 0;
+
+        loop {
+            let c = match self.s[self.pos..].chars().next() {
+                Some(c) => c,
+                None if self.pos == start => return Err(ParseError::new(ParseErrorKind::Empty, start, start)),
+                None => return Err(ParseError::new(ParseErrorKind::InvalidNumber, start, self.pos)),
+            };
+            if !('?'..='~').contains(&c) {
+                return Err(ParseError::new(ParseErrorKind::UnexpectedChar, self.pos, self.pos + c.len_utf8()));
+            }
+            self.pos += 1;
+
+            let chunk = u64::from(c as u8 - 63);
+            chunks += 1;
+            if chunks > MAX_CHUNKS {
+                return Err(ParseError::new(ParseErrorKind::InvalidNumber, start, self.pos));
+            }
+            result |= (chunk & 0x1f) << shift;
+            shift += 5;
+            if chunk < 0x20 {
+                break;
+            }
+        }
+
+        let value = (result >> 1) as i64;
+        Ok(if result & 1 == 1 { !value } else { value })
+    }
+}
@@ -0,0 +1,8 @@
+//! Imports shared by the modules computing with floats
+//!
+//! Float methods (`sqrt()`, `sin()`, ...) are inherent with std, which is also linked by the test
+//! harness. Without std, they come from the `Float` trait, implemented with libm, so the modules
+//! glob-import this module (which is empty with std).
+
+#[cfg(not(any(feature = "std", test)))]
+pub(crate) use num_traits::Float;
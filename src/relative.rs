@@ -0,0 +1,240 @@
+//! Positions given relative to a known point, e.g. `1.2 NM 045° from N 40° 26.767' W 79° 58.933'`
+
+use crate::geodesic::Geodesic;
+use crate::{grid, parse_str, GeoParseError, LatLon, ParseOptions, Point};
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+/// Unit of distance in relative positions
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum DistanceUnit {
+    /// Meters (`m`)
+    #[default]
+    Meters,
+    /// Kilometers (`km`)
+    Kilometers,
+    /// International nautical miles of 1852 m (`NM` or `nmi`)
+    NauticalMiles,
+    /// Statute miles of 1609.344 m (`mi`)
+    Miles,
+    /// International feet of 0.3048 m (`ft`)
+    Feet,
+    /// Yards of 0.9144 m (`yd`)
+    Yards,
+}
+
+impl DistanceUnit {
+    /// Recognize a unit abbreviation (case-insensitive)
+    pub(crate) fn from_suffix(suffix: &str) -> Option<DistanceUnit> {
+        [
+            ("m", DistanceUnit::Meters),
+            ("km", DistanceUnit::Kilometers),
+            ("nm", DistanceUnit::NauticalMiles),
+            ("nmi", DistanceUnit::NauticalMiles),
+            ("mi", DistanceUnit::Miles),
+            ("ft", DistanceUnit::Feet),
+            ("yd", DistanceUnit::Yards),
+        ]
+        .iter()
+        .find(|(s, _)| s.eq_ignore_ascii_case(suffix))
+        .map(|&(_, unit)| unit)
+    }
+
+    /// Get the abbreviation written by `Display`
+    pub fn symbol(self) -> &'static str {
+        match self {
+            DistanceUnit::Meters => "m",
+            DistanceUnit::Kilometers => "km",
+            DistanceUnit::NauticalMiles => "NM",
+            DistanceUnit::Miles => "mi",
+            DistanceUnit::Feet => "ft",
+            DistanceUnit::Yards => "yd",
+        }
+    }
+
+    /// Convert a distance in this unit to meters
+    pub fn to_meters(self, value: f64) -> f64 {
+        value
+            * match self {
+                DistanceUnit::Meters => 1.,
+                DistanceUnit::Kilometers => 1000.,
+                DistanceUnit::NauticalMiles => 1852.,
+                DistanceUnit::Miles => 1609.344,
+                DistanceUnit::Feet => 0.3048,
+                DistanceUnit::Yards => 0.9144,
+            }
+    }
+}
+
+impl Display for DistanceUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// Points of the compass with their bearings (degrees)
+const COMPASS: [(&str, f64); 16] = [
+    ("N", 0.),
+    ("NNE", 22.5),
+    ("NE", 45.),
+    ("ENE", 67.5),
+    ("E", 90.),
+    ("ESE", 112.5),
+    ("SE", 135.),
+    ("SSE", 157.5),
+    ("S", 180.),
+    ("SSW", 202.5),
+    ("SW", 225.),
+    ("WSW", 247.5),
+    ("W", 270.),
+    ("WNW", 292.5),
+    ("NW", 315.),
+    ("NNW", 337.5),
+];
+
+/// Compass directions written out
+const COMPASS_WORDS: [(&str, f64); 12] = [
+    ("north", 0.),
+    ("northeast", 45.),
+    ("north-east", 45.),
+    ("east", 90.),
+    ("southeast", 135.),
+    ("south-east", 135.),
+    ("south", 180.),
+    ("southwest", 225.),
+    ("south-west", 225.),
+    ("west", 270.),
+    ("northwest", 315.),
+    ("north-west", 315.),
+];
+
+/// Position given by a distance and bearing from an anchor point, e.g. `500 m S of 50.08,14.42`.
+///
+/// The `Display` implementation writes e.g. `500 m 180° from 50.08,14.42`. Precision (`{:.5}`) applies
+/// to the anchor coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelativePosition {
+    /// Distance from the anchor, in `unit`
+    pub distance: f64,
+    /// Unit of the distance
+    pub unit: DistanceUnit,
+    /// Bearing from the anchor (degrees clockwise from true north)
+    pub bearing: f64,
+    /// Anchor point (WGS84)
+    pub anchor: Point<f64>,
+}
+
+impl RelativePosition {
+    /// Create from the distance, bearing and anchor (no range checking is done)
+    pub fn new(distance: f64, unit: DistanceUnit, bearing: f64, anchor: Point<f64>) -> RelativePosition {
+        RelativePosition {
+            distance,
+            unit,
+            bearing,
+            anchor,
+        }
+    }
+
+    /// Get the distance in meters
+    pub fn meters(&self) -> f64 {
+        self.unit.to_meters(self.distance)
+    }
+
+    /// Get the absolute point, solving the direct geodesic problem on the WGS84 ellipsoid
+    pub fn to_point(&self) -> Point<f64> {
+        Geodesic::wgs84().destination(self.anchor, self.bearing, self.meters())
+    }
+
+    /// Parse a relative position: distance with a unit, direction, `from` or `of`, and the anchor,
+    /// e.g. `1.2 NM 045° from N 40° 26.767' W 79° 58.933'`, `500 m S of 50.08,14.42`.
+    ///
+    /// - Units are `m`, `km`, `NM` / `nmi` (nautical miles), `mi`, `ft` and `yd` (case-insensitive).
+    /// - The direction is a bearing in degrees (0 to 360, the degree symbol is optional), a point
+    ///   of the 16-point compass (`N`, `NNE`, `NE`, ...) or a word (`north`, `north-east`, ...).
+    /// - The anchor can be in any format supported by `parse()`.
+    pub fn parse<T: AsRef<str> + Display>(text: T) -> Result<RelativePosition, GeoParseError<T>> {
+        match do_parse_relative(text.as_ref()) {
+            Some(p) => Ok(p),
+            None => Err(GeoParseError(text)),
+        }
+    }
+}
+
+/// Parse a relative position and resolve it to an absolute point, see `RelativePosition::parse()`.
+///
+/// ```
+/// let p = latlon::parse_relative("2.5 km NE of 50.08, 14.42").unwrap();
+/// assert!((p.y() - 50.09589).abs() < 1e-5 && (p.x() - 14.44471).abs() < 1e-5);
+/// ```
+pub fn parse_relative<T: AsRef<str> + Display>(text: T) -> Result<Point<f64>, GeoParseError<T>> {
+    match do_parse_relative(text.as_ref()) {
+        Some(p) => Ok(p.to_point()),
+        None => Err(GeoParseError(text)),
+    }
+}
+
+/// Find the first whitespace-delimited `from` or `of`, returning the text before and after it
+fn split_connector(s: &str) -> Option<(&str, &str)> {
+    let mut start = None;
+    for (i, c) in s.char_indices().chain(core::iter::once((s.len(), ' '))) {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(begin)) => {
+                let word = &s[begin..i];
+                if word.eq_ignore_ascii_case("from") || word.eq_ignore_ascii_case("of") {
+                    return Some((&s[..begin], &s[i..]));
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_direction(s: &str) -> Option<f64> {
+    if s.starts_with(|c: char| c.is_ascii_digit()) {
+        let number = s.strip_suffix('°').unwrap_or(s).trim_end();
+        let bearing = grid::parse_decimal(number)?;
+        return if (0f64..=360f64).contains(&bearing) { Some(bearing) } else { None };
+    }
+
+    COMPASS
+        .iter()
+        .chain(COMPASS_WORDS.iter())
+        .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        .map(|&(_, bearing)| bearing)
+}
+
+fn do_parse_relative(s: &str) -> Option<RelativePosition> {
+    let (offset, anchor) = split_connector(s.trim())?;
+
+    let offset = offset.trim_end();
+    let end = offset.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ',')).unwrap_or(offset.len());
+    let distance = grid::parse_decimal(&offset[..end])?;
+
+    let rest = offset[end..].trim_start();
+    let end = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+    let unit = DistanceUnit::from_suffix(&rest[..end])?;
+    let bearing = parse_direction(rest[end..].trim_start())?;
+
+    let anchor = parse_str(anchor, &ParseOptions::default()).ok()?;
+    Some(RelativePosition::new(distance, unit, bearing, anchor))
+}
+
+impl From<RelativePosition> for Point<f64> {
+    fn from(p: RelativePosition) -> Self {
+        p.to_point()
+    }
+}
+
+impl Display for RelativePosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let anchor = LatLon::from(self.anchor);
+        write!(f, "{} {} {}° from ", self.distance, self.unit, self.bearing)?;
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*}", precision, anchor),
+            None => write!(f, "{}", anchor),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! S-JTSK grid coordinates (Křovák oblique conformal conic projection, EPSG method 9819)
+
+use crate::datum::Datum;
+use crate::{grid, GeoParseError, Point};
+use core::f64::consts::FRAC_PI_4;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Latitude of the projection centre (49°30′ N)
+const LAT_C: f64 = 49.5;
+/// Longitude of the origin (24°50′ E of Greenwich, 42°30′ E of Ferro)
+const LNG_0: f64 = 24. + 50. / 60.;
+/// Co-latitude of the cone axis (30°17′17.30311″)
+const ALPHA_C: f64 = 30. + 17. / 60. + 17.30311 / 3600.;
+/// Latitude of the pseudo standard parallel (78°30′ N)
+const LAT_P: f64 = 78.5;
+/// Scale factor on the pseudo standard parallel
+const K_P: f64 = 0.9999;
+
+/// Plausible range of the Y coordinate (m), covering the Czech Republic and Slovakia with a margin
+const Y_RANGE: (f64, f64) = (200_000., 1_000_000.);
+/// Plausible range of the X coordinate (m)
+const X_RANGE: (f64, f64) = (800_000., 1_500_000.);
+
+/// Constants of the projection derived from the ellipsoid
+struct Krovak {
+    e: f64,
+    b: f64,
+    t0: f64,
+    n: f64,
+    r0: f64,
+}
+
+impl Krovak {
+    fn new() -> Krovak {
+        let ellipsoid = Datum::Sjtsk.ellipsoid();
+        let e2 = ellipsoid.e2();
+        let e = e2.sqrt();
+        let phi_c = LAT_C.to_radians();
+        let phi_p = LAT_P.to_radians();
+
+        let a = ellipsoid.a * (1. - e2).sqrt() / (1. - e2 * phi_c.sin().powi(2));
+        let b = (1. + e2 * phi_c.cos().powi(4) / (1. - e2)).sqrt();
+        let gamma0 = (phi_c.sin() / b).asin();
+        let t0 = (FRAC_PI_4 + gamma0 / 2.).tan() * ((1. + e * phi_c.sin()) / (1. - e * phi_c.sin())).powf(e * b / 2.)
+            / (FRAC_PI_4 + phi_c / 2.).tan().powf(b);
+
+        Krovak {
+            e,
+            b,
+            t0,
+            n: phi_p.sin(),
+            r0: K_P * a / phi_p.tan(),
+        }
+    }
+
+    /// Project latitude and longitude (degrees, Bessel) to southing X and westing Y (m)
+    fn forward(&self, lat: f64, lng: f64) -> (f64, f64) {
+        let Krovak { e, b, t0, n, r0, .. } = *self;
+        let phi = lat.to_radians();
+        let alpha_c = ALPHA_C.to_radians();
+        let phi_p = LAT_P.to_radians();
+
+        let esin = e * phi.sin();
+        let u = 2. * ((t0 * (phi / 2. + FRAC_PI_4).tan().powf(b) / ((1. + esin) / (1. - esin)).powf(e * b / 2.)).atan() - FRAC_PI_4);
+        let v = b * (LNG_0 - lng).to_radians();
+        let t = (alpha_c.cos() * u.sin() + alpha_c.sin() * u.cos() * v.cos()).asin();
+        let d = (u.cos() * v.sin() / t.cos()).asin();
+        let theta = n * d;
+        let r = r0 * (FRAC_PI_4 + phi_p / 2.).tan().powf(n) / (t / 2. + FRAC_PI_4).tan().powf(n);
+
+        (r * theta.cos(), r * theta.sin())
+    }
+
+    /// Get latitude and longitude (degrees, Bessel) of southing X and westing Y (m)
+    fn inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        let Krovak { e, b, t0, n, r0, .. } = *self;
+        let alpha_c = ALPHA_C.to_radians();
+        let phi_p = LAT_P.to_radians();
+
+        let r = (x * x + y * y).sqrt();
+        let theta = y.atan2(x);
+        let d = theta / n;
+        let t = 2. * (((r0 / r).powf(1. / n) * (FRAC_PI_4 + phi_p / 2.).tan()).atan() - FRAC_PI_4);
+        let u = (alpha_c.cos() * t.sin() - alpha_c.sin() * t.cos() * d.cos()).asin();
+        let v = (t.cos() * d.sin() / u.cos()).asin();
+
+        let mut phi = u;
+        for _ in 0..10 {
+            let esin = e * phi.sin();
+            let next = 2.
+                * ((t0.powf(-1. / b) * (u / 2. + FRAC_PI_4).tan().powf(1. / b) * ((1. + esin) / (1. - esin)).powf(e / 2.))
+                    .atan()
+                    - FRAC_PI_4);
+            let done = (next - phi).abs() < 1e-12;
+            phi = next;
+            if done {
+                break;
+            }
+        }
+
+        (phi.to_degrees(), LNG_0 - (v / b).to_degrees())
+    }
+}
+
+/// S-JTSK grid coordinates, used by Czech and Slovak cadastral data.
+///
+/// `y` (westing) and `x` (southing) are positive, as in the classical notation. GIS software
+/// (EPSG:5514, e.g. mapy.cz exports) writes them negated and in the reverse order, as easting `-y`
+/// and northing `-x`, e.g. `-743123.45, -1043210.67`.
+///
+/// The `Display` implementation writes the EPSG:5514 form, rounded to centimeters by default
+/// (precision `{:.0}` applies to both numbers). The alternate form (`{:#}`) writes the classical `Y X`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sjtsk {
+    /// Westing (m)
+    pub y: f64,
+    /// Southing (m)
+    pub x: f64,
+}
+
+impl Sjtsk {
+    /// Create from the classical positive westing and southing (no range checking is done)
+    pub fn new(y: f64, x: f64) -> Sjtsk {
+        Sjtsk { y, x }
+    }
+
+    /// Project a WGS84 point
+    pub fn from_point(point: Point<f64>) -> Sjtsk {
+        let bessel = Datum::Wgs84.transform(point, Datum::Sjtsk);
+        let (x, y) = Krovak::new().forward(bessel.y(), bessel.x());
+        Sjtsk { y, x }
+    }
+
+    /// Get the WGS84 point
+    pub fn to_point(&self) -> Point<f64> {
+        let (lat, lng) = Krovak::new().inverse(self.x, self.y);
+        Datum::Sjtsk.to_wgs84(Point::new(lng, lat))
+    }
+
+    /// Parse a pair of S-JTSK coordinates.
+    ///
+    /// Both the EPSG:5514 form (`-743123.45, -1043210.67`) and the classical positive form
+    /// (`743123.45 1043210.67`) are accepted. The numbers must have the same sign, and their order does not
+    /// matter: Y is always smaller than X in the Czech Republic and Slovakia, and the coordinates must be
+    /// in the area around these countries. The numbers can be separated by comma, semicolon, slash or whitespace,
+    /// and decimal comma can be used if the separator is not a comma.
+    pub fn parse<T: AsRef<str> + Display>(text: T) -> Result<Sjtsk, GeoParseError<T>> {
+        match do_parse_sjtsk(text.as_ref()) {
+            Some(p) => Ok(p),
+            None => Err(GeoParseError(text)),
+        }
+    }
+}
+
+fn do_parse_sjtsk(s: &str) -> Option<Sjtsk> {
+    let (a, b) = grid::parse_pair(s)?;
+
+    // both negative (EPSG:5514), or both positive
+    if (a < 0.) != (b < 0.) {
+        return None;
+    }
+
+    let (a, b) = (a.abs(), b.abs());
+    let (y, x) = if a < b { (a, b) } else { (b, a) };
+
+    let in_range = |v: f64, range: (f64, f64)| v >= range.0 && v <= range.1;
+    if !in_range(y, Y_RANGE) || !in_range(x, X_RANGE) {
+        return None;
+    }
+
+    Some(Sjtsk { y, x })
+}
+
+impl From<Sjtsk> for Point<f64> {
+    fn from(s: Sjtsk) -> Self {
+        s.to_point()
+    }
+}
+
+impl Display for Sjtsk {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        if f.alternate() {
+            write!(f, "{:.*} {:.*}", precision, self.y, precision, self.x)
+        } else {
+            write!(f, "{:.*}, {:.*}", precision, -self.y, precision, -self.x)
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Swiss grid coordinates (LV03 and LV95), using the approximate formulas published by swisstopo
+//! (accurate to about a meter)
+
+use crate::{grid, GeoParseError, Point};
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+/// Difference of the LV95 and LV03 false easting (m)
+const LV95_EASTING: f64 = 2_000_000.;
+/// Difference of the LV95 and LV03 false northing (m)
+const LV95_NORTHING: f64 = 1_000_000.;
+
+/// Range of LV03 easting (m), covering Switzerland and Liechtenstein with a margin
+const EASTING_RANGE: (f64, f64) = (400_000., 900_000.);
+/// Range of LV03 northing (m)
+const NORTHING_RANGE: (f64, f64) = (0., 350_000.);
+
+/// Swiss reference frame
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum SwissFrame {
+    /// LV95 (CH1903+), with 7-digit coordinates, e.g. `2600000 / 1200000`
+    #[default]
+    Lv95,
+    /// LV03 (CH1903), the legacy frame with 6-digit coordinates, e.g. `600000 / 200000`
+    Lv03,
+}
+
+/// Swiss grid coordinates, e.g. `2600000 / 1200000` (the old Bern observatory).
+///
+/// The `Display` implementation writes `E / N`, rounded to meters by default (precision `{:.1}`
+/// applies to both numbers).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwissGrid {
+    /// Reference frame
+    pub frame: SwissFrame,
+    /// Easting (m)
+    pub easting: f64,
+    /// Northing (m)
+    pub northing: f64,
+}
+
+impl SwissGrid {
+    /// Create from easting and northing in the frame (no range checking is done)
+    pub fn new(frame: SwissFrame, easting: f64, northing: f64) -> SwissGrid {
+        SwissGrid {
+            frame,
+            easting,
+            northing,
+        }
+    }
+
+    /// Project a WGS84 point. Returns `None` outside the area of Switzerland.
+    pub fn from_point(point: Point<f64>, frame: SwissFrame) -> Option<SwissGrid> {
+        // auxiliary values, in 10 000″ from Bern
+        let phi = (point.y() * 3600. - 169_028.66) / 10_000.;
+        let lambda = (point.x() * 3600. - 26_782.5) / 10_000.;
+        let (phi2, lambda2) = (phi * phi, lambda * lambda);
+
+        let easting = 600_072.37 + 211_455.93 * lambda - 10_938.51 * lambda * phi - 0.36 * lambda * phi2
+            - 44.54 * lambda2 * lambda;
+        let northing = 200_147.07 + 308_807.95 * phi + 3_745.25 * lambda2 + 76.63 * phi2 - 194.56 * lambda2 * phi
+            + 119.79 * phi2 * phi;
+
+        if !in_area(easting, northing) {
+            return None;
+        }
+
+        Some(SwissGrid::new(SwissFrame::Lv03, easting, northing).to_frame(frame))
+    }
+
+    /// Get the WGS84 point
+    pub fn to_point(&self) -> Point<f64> {
+        let lv03 = self.to_frame(SwissFrame::Lv03);
+
+        // auxiliary values, in 1000 km from Bern
+        let y = (lv03.easting - 600_000.) / 1_000_000.;
+        let x = (lv03.northing - 200_000.) / 1_000_000.;
+        let (x2, y2) = (x * x, y * y);
+
+        // in 10 000″
+        let lambda = 2.677_909_4 + 4.728_982 * y + 0.791_484 * y * x + 0.130_6 * y * x2 - 0.043_6 * y2 * y;
+        let phi = 16.902_389_2 + 3.238_272 * x - 0.270_978 * y2 - 0.002_528 * x2 - 0.044_7 * y2 * x - 0.014 * x2 * x;
+
+        Point::new(lambda * 100. / 36., phi * 100. / 36.)
+    }
+
+    /// Get the coordinates in another frame
+    pub fn to_frame(&self, frame: SwissFrame) -> SwissGrid {
+        let (easting, northing) = match (self.frame, frame) {
+            (SwissFrame::Lv03, SwissFrame::Lv95) => (self.easting + LV95_EASTING, self.northing + LV95_NORTHING),
+            (SwissFrame::Lv95, SwissFrame::Lv03) => (self.easting - LV95_EASTING, self.northing - LV95_NORTHING),
+            _ => (self.easting, self.northing),
+        };
+        SwissGrid::new(frame, easting, northing)
+    }
+
+    /// Parse a pair of LV95 or LV03 coordinates.
+    ///
+    /// The frame is recognized by the magnitude of the numbers (`2600000 / 1200000` is LV95,
+    /// `600000 / 200000` is LV03), and their order does not matter, as the easting is always
+    /// the larger number. The numbers can be separated by slash, comma, semicolon or whitespace.
+    pub fn parse<T: AsRef<str> + Display>(text: T) -> Result<SwissGrid, GeoParseError<T>> {
+        match do_parse_swiss(text.as_ref()) {
+            Some(p) => Ok(p),
+            None => Err(GeoParseError(text)),
+        }
+    }
+}
+
+fn in_area(easting: f64, northing: f64) -> bool {
+    let in_range = |v: f64, range: (f64, f64)| v >= range.0 && v <= range.1;
+    in_range(easting, EASTING_RANGE) && in_range(northing, NORTHING_RANGE)
+}
+
+fn do_parse_swiss(s: &str) -> Option<SwissGrid> {
+    let (a, b) = grid::parse_pair(s)?;
+    let (easting, northing) = if a > b { (a, b) } else { (b, a) };
+
+    [SwissFrame::Lv95, SwissFrame::Lv03]
+        .iter()
+        .map(|&frame| SwissGrid::new(frame, easting, northing))
+        .find(|c| {
+            let lv03 = c.to_frame(SwissFrame::Lv03);
+            in_area(lv03.easting, lv03.northing)
+        })
+}
+
+impl From<SwissGrid> for Point<f64> {
+    fn from(s: SwissGrid) -> Self {
+        s.to_point()
+    }
+}
+
+impl Display for SwissGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(0);
+        write!(f, "{:.*} / {:.*}", precision, self.easting, precision, self.northing)
+    }
+}
@@ -1,5 +1,9 @@
-use crate::{parse, parse_lat, parse_lng, GeoParseError};
-use geo_types::Point;
+use crate::{
+    decode_polyline, encode_polyline, format_bbox, great_circle_destination, great_circle_intermediate, great_circle_midpoint, haversine, parse, parse_bbox, parse_lat, parse_lat_str, parse_lat_with, parse_lng, parse_lng_str, parse_line_string, parse_lng_with, parse_many, parse_many_with, parse_polygon, parse_relative, parse_str, parse_with, read_gpx, read_kml, write_gpx, write_kml,
+    AngleUnit, AxisOrder, Datum, DistanceUnit, Dms, Ellipsoid, FixedPoint, FixedScale, GaussKruger, GaussKrugerSystem, GeoParseError, Geodesic, Geohash, Helmert, Hemisphere, IrishGridRef, ItemParseError, LatLon, LatRangePolicy, Latitude,
+    LngRangePolicy, Longitude, Mgrs, OsGridRef, OutOfRange, ParseError, ParseErrorKind, ParseOptions, PathPoint, PointKind, PolylinePrecision, RelativePosition, SignPolicy, Sjtsk, SwissFrame, SwissGrid, Ups, Utm,
+};
+use geo_types::{coord, LineString, Point, Rect};
 
 #[test]
 fn dms_ns_dms_ew() {
@@ -7,7 +11,10 @@ fn dms_ns_dms_ew() {
 
     assert_eq!(reference, parse(r#"40° 26′ 46″ N 79° 58′ 56″ W"#).unwrap(), "normal");
     assert_eq!(reference, parse(r#"40° 26’ 46″ N 79° 58’ 56″ W"#).unwrap(), "fancy apos");
+    assert_eq!(reference, parse(r#"40° 26′ 46.0″ N 79° 58′ 56.0″ W"#).unwrap(), "dec");
+    assert_eq!(reference, parse(r#"40° 26′ 46,0″ N 79° 58′ 56,0″ W"#).unwrap(), "comma dec");
     assert_eq!(reference, parse(r#"40° 26′ 46″ N, 79° 58′ 56″ W"#).unwrap(), "comma");
+    assert_eq!(reference, parse(r#"40° 26′ 46,0″ N, 79° 58′ 56,0″ W"#).unwrap(), "comma dec and comma sep");
     assert_eq!(reference, parse(r#"40° 26′ 46″ N; 79° 58′ 56″ W"#).unwrap(), "semi");
     assert_eq!(reference, parse(r#"40° 26′ 46″ N,79° 58′ 56″ W"#).unwrap(), "comma2");
     assert_eq!(reference, parse(r#"40° 26′ 46″ N;79° 58′ 56″ W"#).unwrap(), "semi2");
@@ -15,6 +22,7 @@ fn dms_ns_dms_ew() {
     assert_eq!(reference, parse(r#"40° 26′ 46″ N ;79° 58′ 56″ W"#).unwrap(), "semi2");
     assert_eq!(reference, parse(r#"40°26′46″N79°58′56″W"#).unwrap(), "compact");
     assert_eq!(reference, parse(r#"40°26′46N79°58′56W"#).unwrap(), "compact, no sec mark");
+    assert_eq!(reference, parse(r#"40°26′46,0″N79°58′56,0″W"#).unwrap(), "compact, comma dec");
     assert_eq!(reference, parse(r#"40 26 46 N 79 58 56 W"#).unwrap(), "no symbols");
     assert_eq!(reference, parse(r#"-40 26 46 S -79 58 56 E"#).unwrap(), "inverted");
 
@@ -36,7 +44,10 @@ fn ns_dms_ew_dms() {
 
     assert_eq!(reference, parse(r#"N 40° 26′ 46″ W 79° 58′ 56″"#).unwrap(), "normal");
     assert_eq!(reference, parse(r#"N 40° 26’ 46″ W 79° 58’ 56″"#).unwrap(), "fancy apos");
+    assert_eq!(reference, parse(r#"N 40° 26′ 46.0″ W 79° 58′ 56.0″"#).unwrap(), "dec");
+    assert_eq!(reference, parse(r#"N 40° 26′ 46,0″ W 79° 58′ 56,0″"#).unwrap(), "comma dec");
     assert_eq!(reference, parse(r#"N 40° 26′ 46″, W 79° 58′ 56″"#).unwrap(), "comma");
+    assert_eq!(reference, parse(r#"N 40° 26′ 46,0″, W 79° 58′ 56,0″"#).unwrap(), "comma dec and comma sep");
     assert_eq!(reference, parse(r#"N 40° 26′ 46″; W 79° 58′ 56″"#).unwrap(), "semi");
     assert_eq!(reference, parse(r#"N 40° 26′ 46″,W 79° 58′ 56″"#).unwrap(), "comma2");
     assert_eq!(reference, parse(r#"N 40° 26′ 46″;W 79° 58′ 56″"#).unwrap(), "semi2");
@@ -44,6 +55,7 @@ fn ns_dms_ew_dms() {
     assert_eq!(reference, parse(r#"N 40° 26′ 46″ ; W 79° 58′ 56″"#).unwrap(), "semi3");
     assert_eq!(reference, parse(r#"N40°26′46″W79°58′56″"#).unwrap(), "compact");
     assert_eq!(reference, parse(r#"N40°26′46W79°58′56"#).unwrap(), "compact, no sec mark");
+    assert_eq!(reference, parse(r#"N40°26′46,0″W79°58′56,0″"#).unwrap(), "compact, comma dec");
     assert_eq!(reference, parse(r#"N 40 26 46 W 79 58 56"#).unwrap(), "no symbols");
     assert_eq!(reference, parse(r#"S -40 26 46 E -79 58 56"#).unwrap(), "inverted");
 
@@ -65,7 +77,10 @@ fn dms_dms() {
 
     assert_eq!(reference, parse(r#"40° 26′ 46″ 79° 58′ 56″"#).unwrap(), "normal");
     assert_eq!(reference, parse(r#"40° 26’ 46″ 79° 58’ 56″"#).unwrap(), "fancy apos");
+    assert_eq!(reference, parse(r#"40° 26′ 46.0″ 79° 58′ 56.0″"#).unwrap(), "dec");
+    assert_eq!(reference, parse(r#"40° 26′ 46,0″ 79° 58′ 56,0″"#).unwrap(), "comma dec");
     assert_eq!(reference, parse(r#"40° 26′ 46″, 79° 58′ 56″"#).unwrap(), "comma");
+    assert_eq!(reference, parse(r#"40° 26′ 46,0″, 79° 58′ 56,0″"#).unwrap(), "comma dec and comma sep");
     assert_eq!(reference, parse(r#"40° 26′ 46″; 79° 58′ 56″"#).unwrap(), "semi");
     assert_eq!(reference, parse(r#"40° 26′ 46″,79° 58′ 56″"#).unwrap(), "comma2");
     assert_eq!(reference, parse(r#"40° 26′ 46″;79° 58′ 56″"#).unwrap(), "semi2");
@@ -73,6 +88,7 @@ fn dms_dms() {
     assert_eq!(reference, parse(r#"40° 26′ 46″ ; 79° 58′ 56″"#).unwrap(), "semi3");
     assert_eq!(reference, parse(r#"40°26′46″79°58′56″"#).unwrap(), "compact");
     assert_eq!(reference, parse(r#"40 26 46 79 58 56"#).unwrap(), "no symbols");
+    assert_eq!(reference, parse(r#"40 26 46,0 79 58 56,0"#).unwrap(), "no symbols, comma dec");
 
     assert_eq!(reference, parse(r#"40° 26’ 46″ 79° 58’ 56″"#).unwrap(), "q1");
     assert_eq!(reference, parse(r#"40° 26' 46” 79° 58' 56”"#).unwrap(), "q2");
@@ -179,6 +195,31 @@ fn dm_dm() {
     parse(r#"-90° 0′ -180° 0′"#).unwrap();
 }
 
+#[test]
+fn mixed_precision() {
+    let reference = 40. + 26. / 60. + 46.5 / 3600.;
+
+    assert_eq!(reference, parse_lat(r#"40° 26′ 46.5″"#).unwrap(), "dec sec");
+    assert_eq!(reference, parse_lat(r#"40° 26′ 46,5″"#).unwrap(), "comma dec sec");
+    assert_eq!(reference, parse_lat(r#"40°26′46,5″N"#).unwrap(), "compact, comma dec sec");
+    assert_eq!(reference, parse_lat(r#"N 40 26 46,5"#).unwrap(), "no symbols, comma dec sec");
+
+    assert_eq!(41., parse_lat(r#"40.5° 30′ N"#).unwrap(), "dec deg with min");
+    assert_eq!(41., parse_lat(r#"40,5° 30′"#).unwrap(), "comma dec deg with min");
+    assert_eq!(40. + 26.5 / 60. + 30. / 3600., parse_lat(r#"40° 26.5′ 30″ N"#).unwrap(), "dec min with sec");
+    assert_eq!(40. + 26.5 / 60. + 30. / 3600., parse_lat(r#"40° 26,5′ 30″ N"#).unwrap(), "comma dec min with sec");
+    assert_eq!(40.5 + 1.5 / 60. + 1.5 / 3600., parse_lat(r#"40.5° 1.5′ 1.5″"#).unwrap(), "dec everything");
+    assert_eq!(40.5 + 1.5 / 60. + 1.5 / 3600., parse_lng(r#"E 40,5° 1,5′ 1,5″"#).unwrap(), "comma dec everything");
+
+    assert_eq!(Point::new(-80., 41.), parse(r#"40.5° 30′ N 79.5° 30′ W"#).unwrap(), "dm pair");
+    assert_eq!(Point::new(80., 41.), parse(r#"40,5° 30′, 79,5° 30′"#).unwrap(), "dm pair, comma dec and comma sep");
+    assert_eq!(
+        Point::new(-(79. + 58. / 60. + 56.5 / 3600.), 40. + 26. / 60. + 46.5 / 3600.),
+        parse(r#"N 40° 26′ 46,5″, W 79° 58′ 56,5″"#).unwrap(),
+        "dms pair, comma dec sec"
+    );
+}
+
 #[test]
 fn d_ns_d_ew() {
     let reference = Point::new(-79.9822, 40.44055);
@@ -217,6 +258,13 @@ fn d_d() {
     assert_eq!(reference, parse(r#"40.44055° ,79.9822°"#).unwrap(), "comma3");
     assert_eq!(reference, parse(r#"40.44055° ;79.9822°"#).unwrap(), "semi3");
 
+    assert_eq!(Point::new(79.5, 40.), parse("40,79.5").unwrap(), "integer lat, comma");
+    assert_eq!(Point::new(79., 40.5), parse("40.5,79").unwrap(), "integer lng, comma");
+    assert_eq!(Point::new(79., 40.), parse("40,79").unwrap(), "integers, comma");
+    assert_eq!(Point::new(79.98258, -40.), parse("-40,79,98258").unwrap(), "integer lat, comma, comma dec lng");
+    assert_eq!(Point::new(14.4215, 50.), parse("50,14,4215").unwrap(), "integer lat, comma, comma dec lng");
+    assert_eq!(Point::new(-14.4215, 50.), parse("50,-14,4215").unwrap(), "integer lat, comma, signed comma dec lng");
+
     parse(r#"90 180"#).unwrap();
     parse(r#"-90 -180"#).unwrap();
     parse(r#"90° 180°"#).unwrap();
@@ -232,6 +280,7 @@ fn dms_nsew() {
 
     assert_eq!(reference, parse_lat(r#"40° 26′ 46″ N"#).unwrap(), "normal");
     assert_eq!(reference, parse_lat(r#"40° 26’ 46″ N"#).unwrap(), "fancy apos");
+    assert_eq!(reference, parse_lat(r#"40° 26′ 46,0″ N"#).unwrap(), "comma dec");
     assert_eq!(reference, parse_lat(r#"40°26′46″N"#).unwrap(), "compact");
     assert_eq!(reference, parse_lat(r#"40°26′46N"#).unwrap(), "compact, no sec mark");
     assert_eq!(reference, parse_lat(r#"40 26 46 N"#).unwrap(), "no symbols");
@@ -239,6 +288,7 @@ fn dms_nsew() {
 
     assert_eq!(reference, parse_lng(r#"40° 26′ 46″ E"#).unwrap(), "normal");
     assert_eq!(reference, parse_lng(r#"40° 26’ 46″ E"#).unwrap(), "fancy apos");
+    assert_eq!(reference, parse_lng(r#"40° 26′ 46,0″ E"#).unwrap(), "comma dec");
     assert_eq!(reference, parse_lng(r#"40°26′46″E"#).unwrap(), "compact");
     assert_eq!(reference, parse_lng(r#"40°26′46E"#).unwrap(), "compact, no sec mark");
     assert_eq!(reference, parse_lng(r#"40 26 46 E"#).unwrap(), "no symbols");
@@ -262,12 +312,14 @@ fn nsew_dms() {
 
     assert_eq!(reference, parse_lat(r#"N 40° 26′ 46″"#).unwrap(), "normal");
     assert_eq!(reference, parse_lat(r#"N 40° 26’ 46″"#).unwrap(), "fancy apos");
+    assert_eq!(reference, parse_lat(r#"N 40° 26′ 46,0″"#).unwrap(), "comma dec");
     assert_eq!(reference, parse_lat(r#"N40°26′46"#).unwrap(), "compact, no sec mark");
     assert_eq!(reference, parse_lat(r#"N 40 26 46"#).unwrap(), "no symbols");
     assert_eq!(reference, parse_lat(r#"S -40 26 46"#).unwrap(), "inverted");
 
     assert_eq!(reference, parse_lng(r#"E 40° 26′ 46″"#).unwrap(), "normal");
     assert_eq!(reference, parse_lng(r#"E 40° 26’ 46″"#).unwrap(), "fancy apos");
+    assert_eq!(reference, parse_lng(r#"E 40° 26′ 46,0″"#).unwrap(), "comma dec");
     assert_eq!(reference, parse_lng(r#"E40°26′46"#).unwrap(), "compact, no sec mark");
     assert_eq!(reference, parse_lng(r#"E 40 26 46"#).unwrap(), "no symbols");
     assert_eq!(reference, parse_lng(r#"W -40 26 46"#).unwrap(), "inverted");
@@ -291,12 +343,14 @@ fn dms() {
 
     assert_eq!(reference, parse_lat(r#"40° 26′ 46″"#).unwrap(), "normal");
     assert_eq!(reference, parse_lat(r#"40° 26’ 46″"#).unwrap(), "fancy apos");
+    assert_eq!(reference, parse_lat(r#"40° 26′ 46,0″"#).unwrap(), "comma dec");
     assert_eq!(reference, parse_lat(r#"40°26′46″"#).unwrap(), "compact");
     assert_eq!(reference, parse_lat(r#"40 26 46"#).unwrap(), "no symbols");
     assert_eq!(ref_neg, parse_lat(r#"-40° 26′ 46″"#).unwrap(), "neg");
 
     assert_eq!(reference, parse_lng(r#"40° 26′ 46″"#).unwrap(), "normal");
     assert_eq!(reference, parse_lng(r#"40° 26’ 46″"#).unwrap(), "fancy apos");
+    assert_eq!(reference, parse_lng(r#"40° 26′ 46,0″"#).unwrap(), "comma dec");
     assert_eq!(reference, parse_lng(r#"40°26′46″"#).unwrap(), "compact");
     assert_eq!(reference, parse_lng(r#"40 26 46"#).unwrap(), "no symbols");
     assert_eq!(ref_neg, parse_lng(r#"-40° 26′ 46″"#).unwrap(), "neg");
@@ -483,7 +537,1035 @@ fn error() {
     assert_eq!("GeoParseError(\"Hello2\")", format!("{:?}", e));
     assert_eq!("GeoParseError(\n    \"Hello2\",\n)", format!("{:#?}", e));
 
+    let owned: GeoParseError<String> = parse("foo").unwrap_err().into_owned();
+    assert_eq!("Error parsing coordinates from foo", owned.to_string());
+
     let e = GeoParseError(std::borrow::Cow::Owned("Foo".to_string()));
     assert_eq!("GeoParseError(\"Foo\")", format!("{:?}", e));
     assert_eq!("GeoParseError(\n    \"Foo\",\n)", format!("{:#?}", e));
 }
+
+#[test]
+fn parse_error() {
+    let opts = ParseOptions::default();
+    let err = |s| parse_str(s, &opts).map(|_| ()).unwrap_err();
+
+    assert_eq!(Point::new(-79.982, 40.446), parse_str(" 40,446 -79,982 ", &opts).unwrap());
+    assert_eq!(40.446, parse_lat_str("40,446° N", &opts).unwrap());
+    assert_eq!(-79.982, parse_lng_str("79,982° W", &opts).unwrap());
+
+    assert_eq!((ParseErrorKind::Empty, 0..0), (err(" ").kind(), err(" ").span()));
+    assert_eq!((ParseErrorKind::UnexpectedChar, 7..8), (err("40.446 # 79.982").kind(), err("40.446 # 79.982").span()));
+    assert_eq!((ParseErrorKind::UnknownUnit, 7..10), (err("40.446 foo").kind(), err("40.446 foo").span()));
+    assert_eq!((ParseErrorKind::InvalidNumber, 7..8), (err("40.446 - 79").kind(), err("40.446 - 79").span()));
+    assert_eq!(ParseErrorKind::TooLong, err("1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17").kind());
+    assert_eq!((ParseErrorKind::Format, 1..12), (err(" 40.446 79 ,").kind(), err(" 40.446 79 ,").span()));
+    assert_eq!((ParseErrorKind::Hemisphere, 0..8), (err("40.446 E, 79.982 W").kind(), err("40.446 E, 79.982 W").span()));
+    assert_eq!((ParseErrorKind::OutOfRange, 7..10), (err("40.446 200").kind(), err("40.446 200").span()));
+
+    let opts = ParseOptions {
+        sign: SignPolicy::Reject,
+        ..Default::default()
+    };
+    let e = parse_lat_str("40 -10.5 N", &opts).unwrap_err();
+    assert_eq!(ParseErrorKind::Format, e.kind());
+    let e = parse_lat_str("-10.5 N", &opts).unwrap_err();
+    assert_eq!(ParseError::new(ParseErrorKind::Sign, 0, 7), e);
+    assert_eq!("Sign conflicts with hemisphere at 0..7", e.to_string());
+
+    // comma decimals longer than the stack buffer are rejected
+    let long = format!("1,{}", "0".repeat(70));
+    assert_eq!(ParseErrorKind::InvalidNumber, parse_lat_str(&long, &opts).unwrap_err().kind());
+    assert_eq!(1., parse_lat_str(&long.replace(',', "."), &opts).unwrap());
+}
+
+#[test]
+fn plus_sign() {
+    assert_eq!(Point::new(79.982, 40.446), parse(r#"+40.446, +79.982"#).unwrap(), "d d");
+    assert_eq!(Point::new(79.982, 40.446), parse(r#"+40.446° N +79.982° E"#).unwrap(), "d ns d ew");
+    assert_eq!(Point::new(-79.98222222222222, 40.44611111111111), parse(r#"N +40 26 46 W +79 58 56"#).unwrap(), "ns dms ew dms");
+    assert_eq!(40.44055, parse_lat(r#"+40° 26.433′"#).unwrap(), "dm");
+    assert_eq!(-40.44055, parse_lng(r#"W +40.44055"#).unwrap(), "nsew d");
+
+    assert!(parse(r#"+-40.446, 79.982"#).is_err());
+    assert!(parse_lat(r#"++40.446"#).is_err());
+}
+
+#[test]
+fn negative_zero() {
+    assert_eq!(-0.5, parse_lat(r#"-0° 30′"#).unwrap());
+    assert_eq!(0.5, parse_lat(r#"-0° 30′ S"#).unwrap());
+    assert_eq!(Point::new(-0.25, -0.5), parse(r#"-0 30, -0 15"#).unwrap());
+}
+
+#[test]
+fn sign_policy() {
+    let flip = ParseOptions { sign: SignPolicy::Flip, ..Default::default() };
+    let reject = ParseOptions { sign: SignPolicy::Reject, ..Default::default() };
+    let redundant = ParseOptions { sign: SignPolicy::Redundant, ..Default::default() };
+
+    assert_eq!(40.5, parse_lat_with("-40.5 S", &flip).unwrap());
+    assert_eq!(-40.5, parse_lat_with("+40.5 S", &flip).unwrap());
+    assert_eq!(-40.5, parse_lng_with("-40.5 E", &flip).unwrap());
+
+    assert!(parse_lat_with("-40.5 S", &reject).is_err());
+    assert!(parse_lat_with("+40.5 N", &reject).is_err());
+    assert!(parse_with("40 26 46 N -79 58 56 W", &reject).is_err());
+    assert_eq!(-40.5, parse_lat_with("40.5 S", &reject).unwrap());
+    assert_eq!(-40.5, parse_lat_with("-40.5", &reject).unwrap());
+
+    assert_eq!(-40.5, parse_lat_with("-40.5 S", &redundant).unwrap());
+    assert_eq!(40.5, parse_lat_with("+40.5 N", &redundant).unwrap());
+    assert_eq!(-40.5, parse_lng_with("W -40.5", &redundant).unwrap());
+    assert!(parse_lat_with("-40.5 N", &redundant).is_err());
+    assert!(parse_lat_with("+40.5 S", &redundant).is_err());
+    assert_eq!(
+        Point::new(-79.98222222222222, -40.44611111111111),
+        parse_with("-40 26 46 S -79 58 56 W", &redundant).unwrap()
+    );
+    assert!(parse_with("-40 26 46 S 79 58 56 E", &redundant).is_ok());
+    assert!(parse_with("-40 26 46 N 79 58 56 E", &redundant).is_err());
+}
+
+#[test]
+fn range_policy() {
+    let wrap = ParseOptions { lng_range: LngRangePolicy::Wrap, ..Default::default() };
+    let keep = ParseOptions { lng_range: LngRangePolicy::Keep, ..Default::default() };
+    let clamp = ParseOptions { lat_range: LatRangePolicy::Clamp, ..Default::default() };
+
+    assert!(parse_lng("280.018").is_err());
+    assert!(parse_lat("90.5").is_err());
+
+    assert!((parse_lng_with("280.018", &wrap).unwrap() - -79.982).abs() < 1e-9, "wrap d");
+    assert_eq!(-80.0, parse_lng_with("280° 0′ 0″ E", &wrap).unwrap(), "wrap dms");
+    assert_eq!(80.0, parse_lng_with("280° 0′ W", &wrap).unwrap(), "wrap dm west");
+    assert_eq!(170.0, parse_lng_with("-190", &wrap).unwrap(), "wrap negative");
+    assert_eq!(180.0, parse_lng_with("-180", &wrap).unwrap(), "wrap antimeridian");
+    assert_eq!(180.0, parse_lng_with("540", &wrap).unwrap(), "wrap 540");
+    assert_eq!(-79.982, parse_lng_with("-79.982", &wrap).unwrap(), "wrap in range");
+
+    assert_eq!(280.018, parse_lng_with("280.018", &keep).unwrap(), "keep");
+    assert_eq!(-280.5, parse_lng_with("280° 30′ W", &keep).unwrap(), "keep west");
+    assert_eq!(Point::new(280.018, 40.446), parse_with("40.446, 280.018", &keep).unwrap(), "keep pair");
+
+    assert_eq!(90.0, parse_lat_with("90.5", &clamp).unwrap(), "clamp");
+    assert_eq!(-90.0, parse_lat_with("91° 0′ S", &clamp).unwrap(), "clamp south");
+    assert_eq!(Point::new(10.0, 90.0), parse_with("95 0 0 N 10 0 0 E", &clamp).unwrap(), "clamp pair");
+    assert!(parse_with("95 0 0 N 190 0 0 E", &clamp).is_err(), "clamp lat only");
+}
+
+#[test]
+fn units() {
+    use std::f64::consts::PI;
+
+    let lat_rad = 0.70592 * (180. / PI);
+    let lng_rad = -1.39595 * (180. / PI);
+
+    assert_eq!(lat_rad, parse_lat(r#"0.70592 rad"#).unwrap(), "rad");
+    assert_eq!(lat_rad, parse_lat(r#"0.70592rad"#).unwrap(), "rad compact");
+    assert_eq!(lat_rad, parse_lat(r#"0,70592 rad N"#).unwrap(), "rad comma dec, ns");
+    assert_eq!(lng_rad, parse_lng(r#"W 1.39595 rad"#).unwrap(), "rad ew");
+    assert_eq!(Point::new(lng_rad, lat_rad), parse(r#"0.70592 rad, -1.39595 rad"#).unwrap(), "rad pair");
+    assert_eq!(Point::new(lng_rad, lat_rad), parse(r#"0.70592rad -1.39595rad"#).unwrap(), "rad pair, space");
+    assert_eq!(Point::new(lng_rad, lat_rad), parse(r#"0.70592 rad N 1.39595 rad W"#).unwrap(), "rad pair, nsew");
+    assert_eq!(Point::new(lng_rad, lat_rad), parse(r#"N 0.70592 rad W 1.39595 rad"#).unwrap(), "rad pair, nsew first");
+
+    assert_eq!(44.94 * 0.9, parse_lat(r#"44.94g"#).unwrap(), "g");
+    assert_eq!(44.94 * 0.9, parse_lat(r#"44.94 gon"#).unwrap(), "gon");
+    assert_eq!(44.94 * 0.9, parse_lat(r#"44.94 grad N"#).unwrap(), "grad");
+    assert_eq!(-180., parse_lng(r#"200 gon W"#).unwrap(), "gon 200");
+    assert!(parse_lat(r#"101 gon"#).is_err(), "gon out of range");
+
+    assert_eq!(719.04 * (360. / 6400.), parse_lat(r#"719.04 mils"#).unwrap(), "mils");
+    assert_eq!(-719.04 * (360. / 6400.), parse_lat(r#"-719.04 mil"#).unwrap(), "mil");
+    assert_eq!(Point::new(-90., 45.), parse(r#"800 mils N, 1600 mils W"#).unwrap(), "mils pair");
+    assert!(parse_lat(r#"1601 mils"#).is_err(), "mils out of range");
+
+    assert_eq!(Point::new(-80., 36.), parse(r#"40 gon, -80°"#).unwrap(), "mixed units");
+
+    let rad = ParseOptions { unit: AngleUnit::Radians, ..Default::default() };
+    assert_eq!(lat_rad, parse_lat_with(r#"0.70592"#, &rad).unwrap(), "default unit");
+    assert_eq!(Point::new(lng_rad, lat_rad), parse_with(r#"0.70592, -1.39595"#, &rad).unwrap(), "default unit pair");
+    assert_eq!(40.5, parse_lat_with(r#"40.5°"#, &rad).unwrap(), "explicit degrees");
+    assert_eq!(40.5, parse_lat_with(r#"40° 30′"#, &rad).unwrap(), "dm always degrees");
+
+    let mils = ParseOptions { unit: AngleUnit::Mils, ..Default::default() };
+    assert_eq!(-90., parse_lng_with(r#"1600 W"#, &mils).unwrap(), "default unit mils");
+
+    assert!(parse_lat(r#"40° 30 rad"#).is_err(), "unit with minutes");
+    assert!(parse_lat(r#"40.5 deg"#).is_err(), "unknown unit");
+}
+
+#[test]
+fn fixed_point() {
+    let p = FixedPoint::parse("404460000, -799820000", FixedScale::E7).unwrap();
+    assert_eq!(404460000, p.lat());
+    assert_eq!(-799820000, p.lng());
+    assert_eq!(FixedScale::E7, p.scale());
+    assert_eq!(Point::new(-79.982, 40.446), Point::from(p));
+
+    assert_eq!(p, FixedPoint::parse("404460000;-799820000", FixedScale::E7).unwrap(), "semi");
+    assert_eq!(p, FixedPoint::parse("  404460000 -799820000 ", FixedScale::E7).unwrap(), "space");
+    assert_eq!(p, FixedPoint::parse("+404460000 , -799820000", FixedScale::E7).unwrap(), "plus");
+
+    let p6 = FixedPoint::parse("40446000,-79982000", FixedScale::E6).unwrap();
+    assert_eq!(Point::new(-79.982, 40.446), Point::from(p6));
+    assert_eq!(p, p6.rescale(FixedScale::E7));
+    assert_eq!(p6, p.rescale(FixedScale::E6));
+    assert_eq!(-2, FixedPoint::new(0, -15, FixedScale::E7).unwrap().rescale(FixedScale::E6).lng(), "round half away");
+
+    assert!(FixedPoint::parse("404460000", FixedScale::E7).is_err());
+    assert!(FixedPoint::parse("404460000, -799820000, 1", FixedScale::E7).is_err());
+    assert!(FixedPoint::parse("40.446, -79.982", FixedScale::E7).is_err());
+    assert!(FixedPoint::parse("904460000, -799820000", FixedScale::E7).is_err());
+    assert!(FixedPoint::new(0, 1_800_000_001, FixedScale::E7).is_none());
+    assert!(FixedPoint::new(i32::MIN, 0, FixedScale::E7).is_none());
+    FixedPoint::new(-900_000_000, 1_800_000_000, FixedScale::E7).unwrap();
+
+    assert_eq!("404460000,-799820000", p.to_string());
+    assert_eq!("40.4460000,-79.9820000", format!("{:#}", p));
+    assert_eq!("-0.000001,0.000000", format!("{:#}", FixedPoint::new(-1, 0, FixedScale::E6).unwrap()));
+    assert_eq!(p, FixedPoint::parse(p.to_string(), FixedScale::E7).unwrap(), "round trip");
+
+    assert_eq!(Some(p), FixedPoint::from_point(Point::new(-79.982, 40.446), FixedScale::E7));
+    assert_eq!(None, FixedPoint::from_point(Point::new(f64::NAN, 40.446), FixedScale::E7));
+    assert_eq!(None, FixedPoint::from_point(Point::new(181., 40.446), FixedScale::E7));
+
+    // lossless round trip through f64
+    for &(lat, lng) in &[(1, -1), (404460000, -799820000), (899999999, 1799999999), (-123456789, 987654321)] {
+        let p = FixedPoint::new(lat, lng, FixedScale::E7).unwrap();
+        assert_eq!(Some(p), FixedPoint::from_point(p.into(), FixedScale::E7));
+    }
+}
+
+#[test]
+fn latitude_longitude() {
+    use std::convert::TryFrom;
+
+    let lat: Latitude = "40° 26′ 46″ N".parse().unwrap();
+    let lng = Longitude::try_from("79° 58′ 56″ W").unwrap();
+    assert_eq!(40.44611111111111, lat.degrees());
+    assert_eq!(-79.98222222222222, f64::from(lng));
+
+    assert!("40° 26′ 46″ E".parse::<Latitude>().is_err(), "wrong hemisphere");
+    assert!(Latitude::try_from("91").is_err(), "out of range");
+    assert_eq!(ParseErrorKind::UnknownUnit, "foo".parse::<Longitude>().unwrap_err().kind());
+
+    assert_eq!(Some(-90.), Latitude::new(-90.).map(Latitude::degrees));
+    assert_eq!(None, Latitude::new(90.1));
+    assert_eq!(None, Latitude::new(f64::NAN));
+    assert_eq!(Some(180.), Longitude::new(180.).map(Longitude::degrees));
+    assert_eq!(None, Longitude::new(-180.1));
+    assert_eq!(Err(OutOfRange), Longitude::try_from(f64::INFINITY));
+
+    assert_eq!("40.446° N", Latitude::new(40.446).unwrap().to_string());
+    assert_eq!("79.982° W", Longitude::new(-79.982).unwrap().to_string());
+    assert_eq!("40.45° S", format!("{:.2}", Latitude::new(-40.446).unwrap()));
+    assert_eq!("40° 26′ 46″ N", format!("{:#.0}", lat));
+    assert_eq!("79° 58′ 56.00″ W", format!("{:#.2}", lng));
+
+    // display round trip
+    for &v in &[0., 40.446, -40.446, 89.99999999, -90.] {
+        let lat = Latitude::new(v).unwrap();
+        assert_eq!(lat, lat.to_string().parse().unwrap());
+    }
+    for &v in &[0., 79.982, -79.982, 179.123456789, -180.] {
+        let lng = Longitude::new(v).unwrap();
+        assert_eq!(lng, lng.to_string().parse().unwrap());
+    }
+}
+
+#[test]
+fn dms_struct() {
+    use std::convert::TryFrom;
+
+    let dms = Latitude::new(-40.5).unwrap().to_dms();
+    assert_eq!(Dms { degrees: 40, minutes: 30, seconds: 0., hemisphere: Hemisphere::South }, dms);
+    assert_eq!(-40.5, dms.to_decimal());
+    assert_eq!(Latitude::new(-40.5), Latitude::try_from(dms).ok());
+    assert_eq!(Err(OutOfRange), Longitude::try_from(dms), "wrong hemisphere");
+
+    let dms = Dms { degrees: 79, minutes: 58, seconds: 56., hemisphere: Hemisphere::West };
+    assert_eq!(parse_lng("79° 58′ 56″ W").unwrap(), Longitude::try_from(dms).unwrap().degrees());
+    assert_eq!("79° 58′ 56″ W", dms.to_string());
+    assert_eq!(Err(OutOfRange), Longitude::try_from(Dms { degrees: 180, minutes: 0, seconds: 1., hemisphere: Hemisphere::East }));
+
+    let dms = Dms::from(Longitude::new(14.449122).unwrap());
+    assert_eq!(14, dms.degrees);
+    assert_eq!(26, dms.minutes);
+    assert!((dms.seconds - 56.8392).abs() < 1e-6);
+    assert_eq!(Hemisphere::East, dms.hemisphere);
+
+    // rounding carries over to minutes and degrees
+    let dms = Dms { degrees: 49, minutes: 59, seconds: 59.9996, hemisphere: Hemisphere::North };
+    assert_eq!("50° 0′ 0.000″ N", format!("{:.3}", dms));
+    assert_eq!("49° 59′ 59.9996″ N", format!("{:.4}", dms));
+}
+
+#[test]
+fn latlon() {
+    use std::convert::TryFrom;
+
+    let ll: LatLon = "40.446,-79.982".parse().unwrap();
+    assert_eq!(LatLon::new(40.446, -79.982), ll);
+    assert_eq!(Point::new(-79.982, 40.446), Point::from(ll));
+    assert_eq!(ll, LatLon::from(Point::new(-79.982, 40.446)));
+    assert_eq!(ll, LatLon::try_from("40° 26′ 45.6″ N 79° 58′ 55.2″ W").unwrap());
+
+    assert!("40.446".parse::<LatLon>().is_err());
+    assert_eq!("GeoParseError(\"foo\")", format!("{:?}", LatLon::try_from("foo").unwrap_err()));
+
+    assert_eq!("40.446,-79.982", ll.to_string());
+    assert_eq!("40.45,-79.98", format!("{:.2}", ll));
+    assert_eq!("40° 26′ 45.6″ N, 79° 58′ 55.2″ W", format!("{:#.1}", ll));
+
+    // display round trip
+    for &(lat, lng) in &[(0., 0.), (-0., -0.), (40.446, -79.982), (-89.123456789012, 179.98765432109876), (90., -180.)] {
+        let ll = LatLon::new(lat, lng);
+        assert_eq!(ll, ll.to_string().parse().unwrap());
+    }
+}
+
+#[test]
+fn utm_mgrs() {
+    let utm = Utm::from_point(Point::new(0., 0.)).unwrap();
+    assert_eq!((31, 'N'), (utm.zone, utm.band));
+    assert!((utm.easting - 166021.4431).abs() < 1e-3);
+    assert!(utm.northing.abs() < 1e-3);
+    assert_eq!("31NAA6602100000", Mgrs::from_point(Point::new(0., 0.)).unwrap().to_string());
+
+    let utm = Utm::from_point(Point::new(-79.982, 40.446)).unwrap();
+    assert_eq!("17T 586329 4477758", utm.to_string());
+    assert_eq!("17T 586328.92 4477758.32", format!("{:.2}", utm));
+    assert_eq!("17TNE8632877758", utm.to_mgrs().to_string());
+    assert_eq!("17TNE863777", format!("{:.3}", utm.to_mgrs()));
+    assert_eq!("17TNE", format!("{:.0}", utm.to_mgrs()));
+
+    // southern hemisphere, even zone
+    let utm = Utm::from_point(Point::new(151.2153, -33.8568)).unwrap();
+    assert_eq!("56H 334901 6252289", utm.to_string());
+    assert_eq!("56HLH3490052288", utm.to_mgrs().to_string());
+
+    // Norway and Svalbard exceptions
+    assert_eq!(32, Utm::from_point(Point::new(5., 60.)).unwrap().zone);
+    assert_eq!(33, Utm::from_point(Point::new(15.65, 78.22)).unwrap().zone);
+    assert_eq!("4QFJ1841756542", Mgrs::from_point(Point::new(-157.8583, 21.3069)).unwrap().to_string());
+
+    assert_eq!(None, Utm::from_point(Point::new(0., 84.1)));
+    assert_eq!(None, Mgrs::from_point(Point::new(0., -80.1)));
+}
+
+#[test]
+fn geohash() {
+    assert_eq!("u4pruydqqvj", Geohash::encode(Point::new(10.40744, 57.64911), 11).unwrap().as_str());
+    assert_eq!("dppn5fyzh", Geohash::encode(Point::new(-79.982, 40.446), 9).unwrap().to_string());
+    assert_eq!("s", Geohash::encode(Point::new(0., 0.), 1).unwrap().to_string());
+    assert_eq!(None, Geohash::encode(Point::new(0., 0.), 13));
+    assert_eq!(None, Geohash::encode(Point::new(0., 91.), 5));
+}
+
+#[test]
+fn datum() {
+    // EPSG Guidance Note 7-2, geographic to geocentric conversion
+    let lat = 53. + 48. / 60. + 33.82 / 3600.;
+    let lng = 2. + 7. / 60. + 46.38 / 3600.;
+    let xyz = Ellipsoid::WGS84.cartesian(lat, lng, 73.);
+    for (a, b) in xyz.iter().zip([3771793.968, 140253.342, 5124304.349].iter()) {
+        assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+    }
+    let (lat2, lng2, h) = Ellipsoid::WGS84.geodetic(xyz);
+    assert!((lat2 - lat).abs() < 1e-9 && (lng2 - lng).abs() < 1e-9 && (h - 73.).abs() < 1e-3);
+
+    // EPSG Guidance Note 7-2, position vector transformation WGS72 to WGS84
+    let wgs72 = Helmert {
+        tz: 4.5,
+        rz: 0.554,
+        s: 0.219,
+        ..Helmert::IDENTITY
+    };
+    let xyz = wgs72.apply([3657660.66, 255768.55, 5201382.11]);
+    for (a, b) in xyz.iter().zip([3657660.78, 255778.43, 5201387.75].iter()) {
+        assert!((a - b).abs() < 0.01, "{} != {}", a, b);
+    }
+
+    // reference points, with the distance (m) allowed for the accuracy of the parameters and the reference
+    let dms = |d: f64, m: f64, s: f64| d.signum() * (d.abs() + m / 60. + s / 3600.);
+    let references = [
+        // EPSG Guidance Note 7-2, geocentric translations example (North Sea), ED50 and WGS84
+        (Datum::Ed50, (dms(2., 7., 51.477), dms(53., 48., 36.563)), (dms(2., 7., 46.38), dms(53., 48., 33.82)), 5.),
+        // NGS datasheet of Meades Ranch (KG0640), the NAD27 origin, and its NAD83 position
+        (Datum::Nad27, (dms(-98., 32., 30.506), dms(39., 13., 26.686)), (dms(-98., 32., 31.7454), dms(39., 13., 26.7122)), 5.),
+        // S-42 origin at the Pulkovo observatory, and its WGS84 position to the arcsecond
+        (Datum::S42, (dms(30., 19., 42.09), dms(59., 46., 18.55)), (dms(30., 19., 34.), dms(59., 46., 18.)), 30.),
+    ];
+    for &(datum, (lng, lat), (wgs84_lng, wgs84_lat), tolerance) in references.iter() {
+        let p = datum.to_wgs84(Point::new(lng, lat));
+        let distance = Geodesic::wgs84().inverse(p, Point::new(wgs84_lng, wgs84_lat)).distance;
+        assert!(distance < tolerance, "{:?}: {} m", datum, distance);
+
+        let back = Datum::Wgs84.transform(p, datum);
+        assert!((back.x() - lng).abs() < 1e-7 && (back.y() - lat).abs() < 1e-7, "{:?}", datum);
+    }
+    assert_eq!(Point::new(14.42, 50.08), Datum::Wgs84.to_wgs84(Point::new(14.42, 50.08)));
+    assert_eq!(Point::new(14.42, 50.08), Datum::Etrs89.to_wgs84(Point::new(14.42, 50.08)));
+    assert_eq!(Point::new(14.42, 50.08), Datum::Wgs84.transform(Point::new(14.42, 50.08), Datum::Etrs89));
+
+    let opts = ParseOptions {
+        datum: Datum::Ed50,
+        ..Default::default()
+    };
+    let p = parse_with("50.08 14.42", &opts).unwrap();
+    assert_eq!(Datum::Ed50.to_wgs84(Point::new(14.42, 50.08)), p);
+    assert!(p.y() < 50.08 && p.x() < 14.42, "ED50 is shifted to the north-east of WGS84 in central Europe");
+}
+
+#[test]
+fn sjtsk() {
+    // EPSG Guidance Note 7-2, Krovak example (in the S-JTSK datum)
+    let lat = 50. + 12. / 60. + 32.442 / 3600.;
+    let lng = 16. + 50. / 60. + 59.179 / 3600.;
+    let s = Sjtsk::from_point(Datum::Sjtsk.to_wgs84(Point::new(lng, lat)));
+    assert!((s.x - 1050538.63).abs() < 0.01 && (s.y - 568991.00).abs() < 0.01, "{:?}", s);
+
+    let p = Datum::Wgs84.transform(s.to_point(), Datum::Sjtsk);
+    assert!((p.y() - lat).abs() < 1e-7 && (p.x() - lng).abs() < 1e-7, "{:?}", p);
+
+    // Prague, Old Town
+    let prague = Sjtsk::parse("-743123.45, -1043210.67").unwrap();
+    assert_eq!(Sjtsk::new(743123.45, 1043210.67), prague);
+    let p = prague.to_point();
+    assert!((p.y() - 50.08532).abs() < 1e-5 && (p.x() - 14.41729).abs() < 1e-5, "{:?}", p);
+    let back = Sjtsk::from_point(p);
+    assert!((back.x - prague.x).abs() < 0.01 && (back.y - prague.y).abs() < 0.01, "{:?}", back);
+
+    assert_eq!(prague, Sjtsk::parse("743123.45 1043210.67").unwrap(), "classical");
+    assert_eq!(prague, Sjtsk::parse("1043210,67; 743123,45").unwrap(), "X first, decimal comma");
+    assert_eq!(prague, Sjtsk::parse("-743123,45 -1043210,67").unwrap(), "decimal comma");
+    assert!(Sjtsk::parse("-743123.45, 1043210.67").is_err(), "mixed signs");
+    assert!(Sjtsk::parse("50.08, 14.42").is_err(), "out of area");
+    assert!(Sjtsk::parse("-743123.45").is_err());
+
+    assert_eq!("-743123.45, -1043210.67", prague.to_string());
+    assert_eq!("743123 1043211", format!("{:#.0}", prague));
+}
+
+#[test]
+fn os_grid() {
+    // Ordnance Survey, A guide to coordinate systems in Great Britain, worked example (in OSGB36)
+    let lat = 52. + 39. / 60. + 27.2531 / 3600.;
+    let lng = 1. + 43. / 60. + 4.5177 / 3600.;
+    let r = OsGridRef::from_point(Datum::Osgb36.to_wgs84(Point::new(lng, lat))).unwrap();
+    assert!((r.easting - 651409.903).abs() < 0.01 && (r.northing - 313177.270).abs() < 0.01, "{:?}", r);
+    assert_eq!("TG 51409 13177", r.to_string());
+    assert_eq!("TG 514 131", format!("{:.3}", r));
+    assert_eq!("TG", format!("{:.0}", r));
+    assert_eq!("651410 313177", format!("{:#}", r));
+
+    let p = Datum::Wgs84.transform(r.to_point(), Datum::Osgb36);
+    assert!((p.y() - lat).abs() < 1e-7 && (p.x() - lng).abs() < 1e-7, "{:?}", p);
+
+    let r = OsGridRef::parse("TQ 30080 80986").unwrap();
+    assert_eq!(OsGridRef::new(530080., 180986.), r);
+    assert_eq!(r, OsGridRef::parse("tq3008080986").unwrap());
+    assert_eq!(r, OsGridRef::parse("530080, 180986").unwrap(), "numeric");
+    let p = r.to_point();
+    assert!((p.y() - 51.51283).abs() < 1e-5 && (p.x() + 0.12684).abs() < 1e-5, "{:?}", p);
+    assert_eq!(Some(r), OsGridRef::from_point(p).map(|r| OsGridRef::new(r.easting.round(), r.northing.round())));
+
+    assert_eq!(OsGridRef::new(438700., 114800.), OsGridRef::parse("SU387148").unwrap());
+    assert_eq!(OsGridRef::new(438700., 114800.), OsGridRef::parse("SU 387 148").unwrap());
+    assert_eq!(OsGridRef::new(210000., 760000.), OsGridRef::parse("NN16").unwrap());
+    assert_eq!(OsGridRef::new(500000., 100000.), OsGridRef::parse("TQ").unwrap());
+    assert_eq!(OsGridRef::new(0., 1200000.), OsGridRef::parse("HL 0 0").unwrap());
+
+    assert!(OsGridRef::parse("SU38714").is_err(), "odd digits");
+    assert!(OsGridRef::parse("SU 387 1480").is_err(), "uneven halves");
+    assert!(OsGridRef::parse("SU 387148 1").is_err());
+    assert!(OsGridRef::parse("SI 387 148").is_err(), "no letter I");
+    assert!(OsGridRef::parse("AA 387 148").is_err(), "outside the grid");
+    assert!(OsGridRef::parse("O 15814 34256").is_err(), "Irish");
+    assert!(OsGridRef::parse("800000, 180986").is_err());
+    for text in ["TQ °…′", "TQ £", "TQ 3£ 8£", "TQ ３００８０８０９８６", "ÓQ 300 809"].iter() {
+        assert!(OsGridRef::parse(*text).is_err(), "non-ASCII {}", text);
+    }
+    assert_eq!(None, OsGridRef::from_point(Point::new(14.42, 50.08)));
+}
+
+#[test]
+fn irish_grid() {
+    // Spire of Dublin, ITM 715830 734697
+    let spire = IrishGridRef::parse("O 15904 34671").unwrap();
+    assert_eq!(IrishGridRef::new(315904., 234671.), spire);
+    let p = spire.to_point();
+    assert!((p.y() - 53.349796).abs() < 1e-5 && (p.x() + 6.260249).abs() < 1e-5, "{:?}", p);
+    let back = IrishGridRef::from_point(p).unwrap();
+    assert!((back.easting - spire.easting).abs() < 0.01 && (back.northing - spire.northing).abs() < 0.01, "{:?}", back);
+
+    assert_eq!("O 15904 34671", spire.to_string());
+    assert_eq!("O 1590 3467", format!("{:.4}", spire));
+    assert_eq!("315904 234671", format!("{:#}", spire));
+
+    assert_eq!(IrishGridRef::new(315800., 234200.), IrishGridRef::parse("O158342").unwrap());
+    assert_eq!(IrishGridRef::new(315814., 234256.), IrishGridRef::parse("315814 234256").unwrap());
+    assert!(IrishGridRef::parse("TQ 30080 80986").is_err(), "British");
+    assert!(IrishGridRef::parse("I 158 342").is_err());
+    assert!(IrishGridRef::parse("O £").is_err(), "non-ASCII");
+    assert!(IrishGridRef::parse("O 1£59").is_err(), "non-ASCII");
+    assert_eq!(None, IrishGridRef::from_point(Point::new(-0.12, 51.5)));
+}
+
+#[test]
+fn swiss_grid() {
+    // swisstopo, Approximate formulas for the transformation between Swiss projection coordinates and WGS84
+    let lat = 46. + 2. / 60. + 38.87 / 3600.;
+    let lng = 8. + 43. / 60. + 49.79 / 3600.;
+    let lv03 = SwissGrid::from_point(Point::new(lng, lat), SwissFrame::Lv03).unwrap();
+    assert!((lv03.easting - 700_000.).abs() < 1. && (lv03.northing - 100_000.).abs() < 1., "{:?}", lv03);
+    assert_eq!("700000 / 100000", lv03.to_string());
+    let lv95 = SwissGrid::from_point(Point::new(lng, lat), SwissFrame::Lv95).unwrap();
+    assert_eq!("2700000 / 1100000", lv95.to_string());
+    assert_eq!(lv95, lv03.to_frame(SwissFrame::Lv95));
+
+    let p = SwissGrid::new(SwissFrame::Lv03, 700_000., 100_000.).to_point();
+    assert!((p.y() - lat).abs() < 1e-5 && (p.x() - lng).abs() < 1e-5, "{:?}", p);
+
+    // Bern
+    let bern = SwissGrid::parse("2600000 / 1200000").unwrap();
+    assert_eq!(SwissGrid::new(SwissFrame::Lv95, 2_600_000., 1_200_000.), bern);
+    let p = bern.to_point();
+    assert!((p.y() - 46.95108).abs() < 1e-4 && (p.x() - 7.43864).abs() < 1e-4, "{:?}", p);
+
+    let lv03 = SwissGrid::parse("600000 / 200000").unwrap();
+    assert_eq!(SwissFrame::Lv03, lv03.frame);
+    assert_eq!(bern.to_point(), lv03.to_point());
+    assert_eq!(lv03, SwissGrid::parse("200000, 600000").unwrap(), "northing first");
+    assert_eq!("600000.0 / 200000.0", format!("{:.1}", lv03));
+
+    assert!(SwissGrid::parse("2600000 / 200000").is_err(), "mixed frames");
+    assert!(SwissGrid::parse("-743123.45, -1043210.67").is_err());
+    assert!(SwissGrid::parse("2600000").is_err());
+    assert_eq!(None, SwissGrid::from_point(Point::new(14.42, 50.08), SwissFrame::Lv95));
+}
+
+#[test]
+fn gauss_kruger() {
+    let dhdn = GaussKrugerSystem::Dhdn;
+    // Munich
+    let gk = GaussKruger::parse("R 4468012 H 5333289", dhdn).unwrap();
+    assert_eq!(GaussKruger::new(dhdn, 4_468_012., 5_333_289.), gk);
+    assert_eq!(4, gk.zone());
+    let p = gk.to_point();
+    assert!((p.y() - 48.13682).abs() < 1e-5 && (p.x() - 11.56877).abs() < 1e-5, "{:?}", p);
+    let back = GaussKruger::from_point(p, dhdn).unwrap();
+    assert!((back.easting - gk.easting).abs() < 0.01 && (back.northing - gk.northing).abs() < 0.01, "{:?}", back);
+
+    assert_eq!("R 4468012 H 5333289", gk.to_string());
+    assert_eq!("4468012.0 5333289.0", format!("{:#.1}", gk));
+
+    for s in &[
+        "Rechtswert: 4468012, Hochwert: 5333289",
+        "H=5333289 R=4468012",
+        "rw 4468012; hw 5333289",
+        "R4468012H5333289",
+        "4468012 5333289",
+        "4468012,0; 5333289,0",
+    ] {
+        assert_eq!(gk, GaussKruger::parse(*s, dhdn).unwrap(), "{}", s);
+    }
+
+    assert!(GaussKruger::parse("R 4468012 R 5333289", dhdn).is_err());
+    assert!(GaussKruger::parse("R 4468012 H 5333289 H", dhdn).is_err());
+    assert!(GaussKruger::parse("X 4468012 Y 5333289", dhdn).is_err());
+    assert!(GaussKruger::parse("R 468012 H 5333289", dhdn).is_err(), "no zone");
+    assert!(GaussKruger::parse("R 4068012 H 5333289", dhdn).is_err(), "too far from the central meridian");
+    assert!(GaussKruger::parse("R 4468012 H -5333289", dhdn).is_err());
+
+    // zone by longitude: 3° zones of DHDN, 6° zones of S-42
+    let berlin = Point::new(13.4, 52.52);
+    assert_eq!(4, GaussKruger::from_point(berlin, dhdn).unwrap().zone());
+    let s42 = GaussKruger::from_point(berlin, GaussKrugerSystem::S42).unwrap();
+    assert_eq!(3, s42.zone());
+    let p = s42.to_point();
+    assert!((p.y() - berlin.y()).abs() < 1e-7 && (p.x() - berlin.x()).abs() < 1e-7, "{:?}", p);
+    let rounded = GaussKruger::new(GaussKrugerSystem::S42, s42.easting.round(), s42.northing.round());
+    assert_eq!("R 3391526 H 5822557", rounded.to_string());
+    assert_eq!(rounded, GaussKruger::parse(rounded.to_string(), GaussKrugerSystem::S42).unwrap());
+    assert_eq!(None, GaussKruger::from_point(Point::new(13.4, -52.52), dhdn));
+}
+
+#[test]
+fn ups() {
+    let z = Ups::parse("Z 2426773 1530125").unwrap();
+    assert_eq!('Z', z.zone);
+    assert!(z.is_north());
+    let p = z.to_point();
+    assert!((p.y() - 84.287232).abs() < 1e-6 && (p.x() - 42.247902).abs() < 1e-6, "{:?}", p);
+    assert_eq!("Z 2426773 1530125", Ups::from_point(p).unwrap().to_string());
+    assert_eq!(z, Ups::parse("z2426773, 1530125").unwrap());
+
+    let pole = Ups::from_point(Point::new(0., 90.)).unwrap();
+    assert_eq!("Z 2000000 2000000", pole.to_string());
+    assert_eq!(Point::new(0., 90.), pole.to_point());
+    assert_eq!(Point::new(0., -90.), Ups::parse("B 2000000 2000000").unwrap().to_point());
+
+    for &(lng, lat, zone) in &[(-45., 85., 'Y'), (135., -85., 'B'), (-100., -89.5, 'A'), (179.9, 84.01, 'Z')] {
+        let ups = Ups::from_point(Point::new(lng, lat)).unwrap();
+        assert_eq!(zone, ups.zone, "{}", ups);
+        let p = Ups::parse(format!("{:.3}", ups)).unwrap().to_point();
+        // longitude error grows towards the pole
+        assert!((p.y() - lat).abs() < 1e-7 && (p.x() - lng).abs() * lat.to_radians().cos() < 1e-7, "{:?}", p);
+    }
+    assert_eq!("Y 1607232.31 1607232.31", format!("{:.2}", Ups::from_point(Point::new(-45., 85.)).unwrap()));
+
+    assert_eq!(None, Ups::from_point(Point::new(14.42, 50.08)));
+    // UTM includes both limits
+    assert_eq!(None, Ups::from_point(Point::new(0., -80.)), "UTM");
+    assert_eq!(None, Ups::from_point(Point::new(0., 84.)), "UTM");
+    assert_eq!('X', Utm::from_point(Point::new(0., 84.)).unwrap().band);
+    assert_eq!('C', Utm::from_point(Point::new(0., -80.)).unwrap().band);
+    assert!(Ups::from_point(Point::new(0., -80.000001)).is_some());
+    assert!(Ups::parse("Y 2426773 1530125").is_err(), "wrong side");
+    assert!(Ups::parse("B 2426773 1530125").unwrap().to_point().y() < -84., "southern");
+    assert!(Ups::parse("Z 3426773 1530125").is_err(), "out of the polar region");
+    assert!(Ups::parse("C 2426773 1530125").is_err());
+    assert!(Ups::parse("Z 2426773").is_err());
+}
+
+#[test]
+fn geodesic_inverse() {
+    let g = Geodesic::wgs84();
+    let dms = |d: f64, m: f64, s: f64| d.signum() * (d.abs() + m / 60. + s / 3600.);
+
+    // Vincenty (1975) / Geoscience Australia: Flinders Peak to Buninyong
+    let flinders = Point::new(dms(144., 25., 29.5244), dms(-37., 57., 3.7203));
+    let buninyong = Point::new(dms(143., 55., 35.3839), dms(-37., 39., 10.1561));
+    let r = g.inverse(flinders, buninyong);
+    assert!((r.distance - 54972.271).abs() < 0.001, "{:?}", r);
+    assert!((r.initial_azimuth - (dms(306., 52., 5.37) - 360.)).abs() < 0.01 / 3600., "{:?}", r);
+    assert!((r.final_azimuth - (dms(307., 10., 25.07) - 360.)).abs() < 0.01 / 3600., "{:?}", r);
+
+    // GeographicLib documentation: Wellington to Salamanca
+    let r = g.inverse(Point::new(174.81, -41.32), Point::new(-5.50, 40.96));
+    assert!((r.distance - 19959679.267).abs() < 0.001, "{:?}", r);
+    assert!((r.initial_azimuth - 161.067669986).abs() < 1e-9, "{:?}", r);
+    assert!((r.final_azimuth - 18.825195123).abs() < 1e-9, "{:?}", r);
+
+    // nearly antipodal, where Vincenty's method does not converge
+    let r = g.inverse(Point::new(0., 0.), Point::new(179.5, 0.5));
+    assert!((r.distance - 19936288.579).abs() < 0.001, "{:?}", r);
+
+    // along the equator, along a meridian, and antipodal over the pole
+    assert!((g.distance(Point::new(0., 0.), Point::new(1., 0.)) - 111319.490793).abs() < 1e-6);
+    let r = g.inverse(Point::new(14., 0.), Point::new(14., 10.));
+    assert_eq!((0., 0.), (r.initial_azimuth, r.final_azimuth));
+    let r = g.inverse(Point::new(0., 0.), Point::new(180., 0.));
+    assert!((r.distance - 20003931.458625).abs() < 1e-6, "{:?}", r);
+    assert_eq!((0., 180.), (r.initial_azimuth, r.final_azimuth));
+
+    assert_eq!(0., g.distance(Point::new(14.42, 50.08), Point::new(14.42, 50.08)));
+
+    // reversed
+    let r = g.inverse(buninyong, flinders);
+    assert!((r.distance - 54972.271).abs() < 0.001, "{:?}", r);
+    assert!((r.initial_azimuth - dms(127., 10., 25.07)).abs() < 0.01 / 3600., "{:?}", r);
+
+    // spherical
+    let r = haversine(Point::new(0., 0.), Point::new(1., 0.));
+    assert!((r.distance - 111195.080).abs() < 0.001, "{:?}", r);
+    assert_eq!((90., 90.), (r.initial_azimuth, r.final_azimuth));
+    let r = haversine(flinders, buninyong);
+    assert!((r.distance - 54972.271).abs() < 100., "{:?}", r);
+    assert!((r.initial_azimuth + 53.0161).abs() < 1e-4 && (r.final_azimuth + 52.7107).abs() < 1e-4, "{:?}", r);
+}
+
+#[test]
+fn geodesic_direct() {
+    let g = Geodesic::wgs84();
+    let dms = |d: f64, m: f64, s: f64| d.signum() * (d.abs() + m / 60. + s / 3600.);
+
+    // Vincenty (1975) / Geoscience Australia: Flinders Peak to Buninyong
+    let flinders = Point::new(dms(144., 25., 29.5244), dms(-37., 57., 3.7203));
+    let r = g.direct(flinders, dms(306., 52., 5.37), 54972.271);
+    assert!((r.point.y() - dms(-37., 39., 10.1561)).abs() < 1e-4 / 3600., "{:?}", r);
+    assert!((r.point.x() - dms(143., 55., 35.3839)).abs() < 1e-4 / 3600., "{:?}", r);
+    assert!((r.final_azimuth - (dms(307., 10., 25.07) - 360.)).abs() < 0.01 / 3600., "{:?}", r);
+
+    // back along the inverse
+    let start = parse("N 50°5.30385' E 14°26.94732'").unwrap();
+    let p = g.destination(start, 135., 2500.);
+    let back = g.inverse(start, p);
+    assert!((back.distance - 2500.).abs() < 1e-6 && (back.initial_azimuth - 135.).abs() < 1e-9, "{:?}", back);
+    let q = great_circle_destination(start, 135., 2500.);
+    assert!(g.distance(p, q) < 10., "{:?} {:?}", p, q);
+
+    // over the pole, and across the antimeridian
+    let r = g.direct(Point::new(0., 0.), 0., 20003931.458625);
+    assert!(r.point.y().abs() < 1e-9 && r.point.x() == 180. && r.final_azimuth == 180., "{:?}", r);
+    let p = g.destination(Point::new(170., 0.), 90., 111319.490793 * 20.);
+    assert!((p.x() + 170.).abs() < 1e-9 && p.y() == 0., "{:?}", p);
+    let p = g.destination(Point::new(14., 50.), 90., -10000.);
+    assert!(p.x() < 14., "negative distance {:?}", p);
+
+    let p = g.midpoint(Point::new(0., 0.), Point::new(90., 0.));
+    assert!((p.x() - 45.).abs() < 1e-12 && p.y() == 0., "{:?}", p);
+    let p = g.intermediate(Point::new(0., 0.), Point::new(90., 0.), 0.25);
+    assert!((p.x() - 22.5).abs() < 1e-12 && p.y() == 0., "{:?}", p);
+    let p = great_circle_midpoint(Point::new(-10., 50.), Point::new(10., 50.));
+    assert!(p.x().abs() < 1e-12 && (p.y() - 50.431313).abs() < 1e-6, "{:?}", p);
+    let p = great_circle_intermediate(Point::new(0., 0.), Point::new(90., 0.), 1.);
+    assert!((p.x() - 90.).abs() < 1e-9 && p.y().abs() < 1e-9, "{:?}", p);
+}
+
+#[test]
+fn relative_position() {
+    let g = Geodesic::wgs84();
+
+    let r = RelativePosition::parse("1.2 NM 045° from N 40° 26.767' W 79° 58.933'").unwrap();
+    assert_eq!(DistanceUnit::NauticalMiles, r.unit);
+    assert_eq!((1.2, 45.), (r.distance, r.bearing));
+    assert_eq!(parse("N 40° 26.767' W 79° 58.933'").unwrap(), r.anchor);
+    let path = g.inverse(r.anchor, r.to_point());
+    assert!((path.distance - 2222.4).abs() < 1e-6 && (path.initial_azimuth - 45.).abs() < 1e-9, "{:?}", path);
+
+    let anchor = Point::new(14.42, 50.08);
+    let south = parse_relative("500 m S of 50.08,14.42").unwrap();
+    assert!((south.x() - 14.42).abs() < 1e-12 && (g.distance(anchor, south) - 500.).abs() < 1e-6, "{:?}", south);
+    assert!(south.y() < 50.08);
+
+    for (text, bearing, meters) in [
+        ("2.5 km NE of 50.08, 14.42", 45., 2500.),
+        ("2,5km NNW from 50.08 14.42", 337.5, 2500.),
+        ("3 mi north-west of 50.08,14.42", 315., 4828.032),
+        ("100 ft 90 FROM 50.08,14.42", 90., 30.48),
+        ("1 nmi east of 50.08,14.42", 90., 1852.),
+        ("0 yd 360° of N 50.08 E 14.42", 360., 0.),
+    ]
+    .iter()
+    {
+        let r = RelativePosition::parse(*text).unwrap();
+        assert_eq!((*bearing, *meters), (r.bearing, r.meters()), "{}", text);
+        assert_eq!(anchor, r.anchor, "{}", text);
+    }
+
+    for text in [
+        "",
+        "500 m S 50.08,14.42",
+        "500 S of 50.08,14.42",
+        "-500 m S of 50.08,14.42",
+        "500 lightyears S of 50.08,14.42",
+        "500 m SSS of 50.08,14.42",
+        "500 m 361° of 50.08,14.42",
+        "500 m of 50.08,14.42",
+        "500 m S of",
+        "500 m S of 95,14.42",
+    ]
+    .iter()
+    {
+        assert!(RelativePosition::parse(*text).is_err(), "{}", text);
+    }
+
+    assert_eq!("500 m 180° from 50.08,14.42", RelativePosition::new(500., DistanceUnit::Meters, 180., anchor).to_string());
+    let r = RelativePosition::parse("1.5 km 22.5 of 50.08,14.42").unwrap();
+    assert_eq!("1.5 km 22.5° from 50.08000,14.42000", format!("{:.5}", r));
+    assert_eq!(r, RelativePosition::parse(r.to_string()).unwrap());
+}
+
+#[test]
+fn bbox() {
+    let reference = Rect::new(coord! { x: 14.2, y: 49.9 }, coord! { x: 14.7, y: 50.2 });
+
+    for (text, order) in [
+        ("14.2,49.9,14.7,50.2", AxisOrder::LngLat),
+        ("bbox=14.2,49.9,14.7,50.2", AxisOrder::LngLat),
+        ("BBOX=14.2, 49.9, 14.7, 50.2", AxisOrder::LngLat),
+        ("14.2 49.9 14.7 50.2", AxisOrder::LngLat),
+        ("[14.2,49.9,14.7,50.2]", AxisOrder::LngLat),
+        ("14.2,50.2,14.7,49.9", AxisOrder::LngLat),
+        ("49.9,14.2,50.2,14.7", AxisOrder::LatLng),
+        ("[[49.9,14.2],[50.2,14.7]]", AxisOrder::LatLng),
+        ("[[49.9, 14.2], [50.2, 14.7]]", AxisOrder::LatLng),
+        ("N 49°54' E 14°12', N 50°12' E 14°42'", AxisOrder::LngLat),
+        ("49°54′N 14°12′E; 50°12′N 14°42′E", AxisOrder::LatLng),
+        ("N 49.9 E 14.2 N 50.2 E 14.7", AxisOrder::LngLat),
+        ("49.9 N 14.2 E - 50.2 N 14.7 E", AxisOrder::LngLat),
+        ("49°54' 14°12' / 50°12' 14°42'", AxisOrder::LngLat),
+    ]
+    .iter()
+    {
+        let rect = parse_bbox(*text, *order).unwrap_or_else(|e| panic!("{}", e));
+        assert!((rect.min().x - 14.2).abs() < 1e-12 && (rect.max().x - 14.7).abs() < 1e-12, "{} {:?}", text, rect);
+        assert!((rect.min().y - 49.9).abs() < 1e-12 && (rect.max().y - 50.2).abs() < 1e-12, "{} {:?}", text, rect);
+    }
+
+    // crossing the antimeridian
+    let fiji = parse_bbox("177,-19,-178,-16", AxisOrder::LngLat).unwrap();
+    assert_eq!((177., -19.), fiji.min().x_y());
+    assert_eq!((182., -16.), fiji.max().x_y());
+    assert_eq!(fiji, parse_bbox("S 19 E 177 S 16 W 178", AxisOrder::LngLat).unwrap());
+    assert_eq!("177,-19,-178,-16", format_bbox(fiji, AxisOrder::LngLat).to_string());
+
+    for text in [
+        "",
+        "14.2,49.9,14.7",
+        "14.2,49.9,14.7,50.2,3",
+        "14.2,49.9,14.7,95",
+        "190,49.9,14.7,50.2",
+        "bbox:14.2,49.9,14.7,50.2",
+        "N 49°54' E 14°12'",
+        "N 49°54' E 14°12' N 50°12'",
+        "49.9 N 14.2 E-50.2 N 14.7 E",
+    ]
+    .iter()
+    {
+        assert!(parse_bbox(*text, AxisOrder::LngLat).is_err(), "{}", text);
+    }
+
+    assert_eq!("14.2,49.9,14.7,50.2", format_bbox(reference, AxisOrder::LngLat).to_string());
+    assert_eq!("49.90,14.20,50.20,14.70", format!("{:.2}", format_bbox(reference, AxisOrder::LatLng)));
+    assert_eq!("[[49.9,14.2],[50.2,14.7]]", format!("{:#}", format_bbox(reference, AxisOrder::LatLng)));
+    assert_eq!("[[14.2,49.9],[14.7,50.2]]", format!("{:#}", format_bbox(reference, AxisOrder::LngLat)));
+    for order in [AxisOrder::LngLat, AxisOrder::LatLng].iter() {
+        for text in [format_bbox(reference, *order).to_string(), format!("{:#}", format_bbox(reference, *order))].iter() {
+            assert_eq!(reference, parse_bbox(text, *order).unwrap(), "{}", text);
+        }
+    }
+}
+
+#[test]
+fn coordinate_list() {
+    let expected = [Point::new(14.42, 50.08), Point::new(16.61, 49.19), Point::new(16.37, 48.2)];
+
+    for text in [
+        "50.08, 14.42\n49.19, 16.61\n48.2, 16.37",
+        "50.08,14.42;49.19,16.61;48.2,16.37",
+        "50.08 14.42; 49.19 16.61; 48.2 16.37;",
+        "\r\n  50.08, 14.42\r\n\r\n49.19 16.61 ;\r\n 48.2,16.37\r\n",
+        "N 50°4.8' E 14°25.2'\n49°11'24\"N 16°36'36\"E; 48.2° N 16.37° E",
+    ]
+    .iter()
+    {
+        let points = parse_many(text).unwrap_or_else(|e| panic!("{:?}: {}", text, e));
+        assert_eq!(expected.len(), points.len(), "{:?}", text);
+        for (a, b) in expected.iter().zip(points.iter()) {
+            assert!((a.x() - b.x()).abs() < 1e-12 && (a.y() - b.y()).abs() < 1e-12, "{:?}: {:?}", text, points);
+        }
+    }
+    assert_eq!(Vec::<Point<f64>>::new(), parse_many(" \n ;").unwrap());
+
+    let text = "50.08, 14.42\n\n49.19, 16.61; 48.2 N 16.37 N; 1, 2";
+    let err = parse_many(text).unwrap_err();
+    assert_eq!(2, err.index());
+    assert_eq!(ParseErrorKind::Hemisphere, err.error().kind());
+    assert_eq!("16.37 N", &text[err.error().span()]);
+    assert_eq!(1, parse_many("50.08, 14.42\n50.08; 14.42").unwrap_err().index());
+
+    let opts = ParseOptions {
+        lng_range: LngRangePolicy::Wrap,
+        ..ParseOptions::default()
+    };
+    assert_eq!(vec![Point::new(-170., 10.)], parse_many_with("10, 190", &opts).unwrap());
+
+    let line = parse_line_string("50.08, 14.42; 49.19, 16.61").unwrap();
+    assert_eq!(LineString::from(vec![(14.42, 50.08), (16.61, 49.19)]), line);
+    let err: ItemParseError = parse_line_string("50.08, 14.42\n").unwrap_err();
+    assert_eq!((1, ParseErrorKind::Empty, 12..12), (err.index(), err.error().kind(), err.error().span()));
+
+    let polygon = parse_polygon("0, 0; 0, 1; 1, 1").unwrap();
+    assert_eq!(4, polygon.exterior().0.len());
+    assert!(polygon.exterior().is_closed() && polygon.interiors().is_empty());
+    assert_eq!(polygon, parse_polygon("0, 0; 0, 1; 1, 1; 0, 0").unwrap());
+    assert_eq!(2, parse_polygon("0, 0; 0, 1").unwrap_err().index());
+    // degenerate rings
+    let err = parse_polygon("0, 0; 0, 1; 0, 0").unwrap_err();
+    assert_eq!((3, ParseErrorKind::Empty, 16..16), (err.index(), err.error().kind(), err.error().span()));
+    assert_eq!(4, parse_polygon("0, 0; 0, 1; 0, 1; 0, 0").unwrap_err().index());
+    assert_eq!(4, parse_polygon("0, 0; 0, 1; 0, 0; 0, 1").unwrap_err().index());
+    assert_eq!(6, parse_polygon("0, 0; 0, 1; 0, 0; 0, 1; 1, 1").unwrap().exterior().0.len());
+    assert_eq!("Item 1: Unrecognized format at 6..11", parse_polygon("0, 0; 0 1 2; 1, 1").unwrap_err().to_string());
+}
+
+#[test]
+fn polyline() {
+    let route = [Point::new(-120.2, 38.5), Point::new(-120.95, 40.7), Point::new(-126.453, 43.252)];
+    let encoded = "_p~iF~ps|U_ulLnnqC_mqNvxq`@";
+    assert_eq!(encoded, encode_polyline(route.iter().copied(), PolylinePrecision::E5).unwrap());
+    assert_eq!(route.to_vec(), decode_polyline(encoded, PolylinePrecision::E5).unwrap());
+
+    let e6 = encode_polyline(route.iter().copied(), PolylinePrecision::E6).unwrap();
+    assert_eq!("_izlhA~rlgdF_{geC~ywl@_kwzCn`{nI", e6);
+    assert_eq!(route.to_vec(), decode_polyline(&e6, PolylinePrecision::E6).unwrap());
+
+    // rounding errors do not accumulate
+    let points: Vec<_> = (0..100).map(|i| Point::new(14.4 + f64::from(i) * 0.0000037, 50.08 - f64::from(i) * 0.0000061)).collect();
+    let decoded = decode_polyline(&encode_polyline(points.iter().copied(), PolylinePrecision::E5).unwrap(), PolylinePrecision::E5).unwrap();
+    for (a, b) in points.iter().zip(decoded.iter()) {
+        assert!((a.x() - b.x()).abs() <= 0.5e-5 + 1e-12 && (a.y() - b.y()).abs() <= 0.5e-5 + 1e-12, "{:?} {:?}", a, b);
+    }
+
+    let line = LineString::from(route.to_vec());
+    assert_eq!(encoded, encode_polyline(line.points(), PolylinePrecision::E5).unwrap());
+    assert_eq!(line, LineString::from(decode_polyline(encoded, PolylinePrecision::E5).unwrap()));
+
+    assert_eq!("", encode_polyline(Vec::new(), PolylinePrecision::E5).unwrap());
+    assert!(decode_polyline("", PolylinePrecision::E5).unwrap().is_empty());
+    assert_eq!("??", encode_polyline(vec![Point::new(0., 0.)], PolylinePrecision::E6).unwrap());
+    assert_eq!(
+        vec![Point::new(180., -90.), Point::new(-180., 90.)],
+        decode_polyline(&encode_polyline(vec![Point::new(180., -90.), Point::new(-180., 90.)], PolylinePrecision::E6).unwrap(), PolylinePrecision::E6).unwrap()
+    );
+    assert_eq!(Err(OutOfRange), encode_polyline(vec![Point::new(f64::NEG_INFINITY, 0.), Point::new(f64::INFINITY, 0.)], PolylinePrecision::E5));
+    assert_eq!(Err(OutOfRange), encode_polyline(vec![Point::new(0., f64::NAN)], PolylinePrecision::E5));
+    assert_eq!(Err(OutOfRange), encode_polyline(vec![Point::new(0., 0.), Point::new(180.1, 0.)], PolylinePrecision::E6));
+
+    for (text, kind, span) in [
+        ("_p~iF ~ps|U", ParseErrorKind::UnexpectedChar, 5..6),
+        ("_p~iF~ps|U_ulL", ParseErrorKind::InvalidNumber, 10..14),
+        ("_p~iF~ps|U_ulLnnq", ParseErrorKind::InvalidNumber, 14..17),
+        ("_p~iF~ps|U_ulLnnqé", ParseErrorKind::UnexpectedChar, 17..19),
+        ("~~~~~~~~~~~~~~~", ParseErrorKind::InvalidNumber, 0..8),
+        // no high bits dropped from over-long values
+        ("~~~~~~~~????", ParseErrorKind::InvalidNumber, 0..8),
+        ("_gsia@?", ParseErrorKind::OutOfRange, 0..7),
+    ]
+    .iter()
+    {
+        let err = decode_polyline(text, PolylinePrecision::E5).unwrap_err();
+        assert_eq!((*kind, span.clone()), (err.kind(), err.span()), "{}", text);
+    }
+}
+
+#[test]
+fn gpx() {
+    let doc = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- <wpt lat="1" lon="1"/> commented out -->
+<gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1">
+  <metadata><name>Trip > Prague</name></metadata>
+  <wpt lon='14.42' lat='50.08'><name><![CDATA[<trkpt lat="2" lon="2"/>]]></name></wpt>
+  <rte><rtept lat="49.19" lon="16.61"/></rte>
+  <trk><name>Walk</name><trkseg>
+    <trkpt lat="50.0875" lon="14.4213">
+      <ele> 235.5 </ele>
+      <time>2024-05-01T08:30:00Z</time>
+      <extensions><gpxtpx:TrackPointExtension><gpxtpx:hr>120</gpxtpx:hr></gpxtpx:TrackPointExtension></extensions>
+    </trkpt>
+    <gpx:trkpt lat="-33.8568" lon="151.2153" ><gpx:ele>-2</gpx:ele></gpx:trkpt>
+  </trkseg></trk>
+</gpx>"#;
+
+    let points: Vec<PathPoint> = read_gpx(doc).collect::<Result<_, _>>().unwrap();
+    assert_eq!(
+        vec![
+            (PointKind::Waypoint, (14.42, 50.08), None, None),
+            (PointKind::RoutePoint, (16.61, 49.19), None, None),
+            (PointKind::TrackPoint, (14.4213, 50.0875), Some(235.5), Some("2024-05-01T08:30:00Z")),
+            (PointKind::TrackPoint, (151.2153, -33.8568), Some(-2.), None),
+        ],
+        points.iter().map(|p| (p.kind, p.point.x_y(), p.elevation, p.time)).collect::<Vec<_>>()
+    );
+
+    let doc = r#"<gpx><wpt lat="50.08"/><wpt lat="95" lon="14"/><wpt lat="x" lon="14"/>
+        <wpt lat="1" lon="2"><ele>high</ele></wpt><wpt lat="3" lon="4"/><wpt lat="5" lon="6">"#;
+    let results: Vec<_> = read_gpx(doc).collect();
+    let errors: Vec<_> = results.iter().filter_map(|r| r.as_ref().err()).map(|e| (e.kind(), &doc[e.span()])).collect();
+    assert_eq!(
+        vec![
+            (ParseErrorKind::Format, r#"<wpt lat="50.08"/>"#),
+            (ParseErrorKind::OutOfRange, "95"),
+            (ParseErrorKind::InvalidNumber, "x"),
+            (ParseErrorKind::InvalidNumber, "high"),
+            (ParseErrorKind::Format, r#"<wpt lat="5" lon="6">"#),
+        ],
+        errors
+    );
+    assert_eq!(Point::new(4., 3.), results[4].unwrap().point);
+    assert_eq!(0, read_gpx("no markup").count());
+
+    let track = write_gpx(points.iter().copied(), PointKind::TrackPoint);
+    assert_eq!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="latlon" xmlns="http://www.topografix.com/GPX/1/1">
+<trk><trkseg>
+<trkpt lat="50.08" lon="14.42"/>
+<trkpt lat="49.19" lon="16.61"/>
+<trkpt lat="50.0875" lon="14.4213"><ele>235.5</ele><time>2024-05-01T08:30:00Z</time></trkpt>
+<trkpt lat="-33.8568" lon="151.2153"><ele>-2</ele></trkpt>
+</trkseg></trk>
+</gpx>
+"#,
+        track
+    );
+    let read: Vec<PathPoint> = read_gpx(&track).collect::<Result<_, _>>().unwrap();
+    assert!(read.iter().all(|p| p.kind == PointKind::TrackPoint));
+    assert_eq!(
+        points.iter().map(|p| (p.point, p.elevation, p.time)).collect::<Vec<_>>(),
+        read.iter().map(|p| (p.point, p.elevation, p.time)).collect::<Vec<_>>()
+    );
+
+    let route = write_gpx(vec![Point::new(14.42, 50.08)], PointKind::RoutePoint);
+    assert!(route.contains("<rte>\n<rtept lat=\"50.08\" lon=\"14.42\"/>\n</rte>\n"), "{}", route);
+    let odd = PathPoint {
+        time: Some("<now>"),
+        ..PathPoint::from(Point::new(0., 0.))
+    };
+    assert!(write_gpx(vec![odd], PointKind::Waypoint).contains("<time>&lt;now&gt;</time></wpt>"));
+}
+
+#[test]
+fn kml() {
+    let doc = r#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2"><Document>
+  <Placemark><name>Prague</name><Point><coordinates>14.42,50.08,235</coordinates></Point></Placemark>
+  <Placemark><LineString><tessellate>1</tessellate>
+    <coordinates>
+      16.61,49.19
+      -79.982,40.446,0   151.2153,-33.8568
+    </coordinates>
+  </LineString></Placemark>
+  <Placemark><Polygon><outerBoundaryIs><LinearRing><kml:coordinates>0,0 1,0 0,1 0,0</kml:coordinates></LinearRing></outerBoundaryIs></Polygon></Placemark>
+  <Placemark><Point><coordinates/></Point></Placemark>
+</Document></kml>"#;
+
+    let points: Vec<PathPoint> = read_kml(doc).collect::<Result<_, _>>().unwrap();
+    assert_eq!(8, points.len());
+    assert!(points.iter().all(|p| p.kind == PointKind::Coordinate && p.time.is_none()));
+    assert_eq!(
+        vec![
+            ((14.42, 50.08), Some(235.)),
+            ((16.61, 49.19), None),
+            ((-79.982, 40.446), Some(0.)),
+            ((151.2153, -33.8568), None),
+        ],
+        points[..4].iter().map(|p| (p.point.x_y(), p.elevation)).collect::<Vec<_>>()
+    );
+
+    let doc = "<coordinates>1,2 1 x,2 1,2,3,4 1,95 ,2 3,4</coordinates>";
+    let results: Vec<_> = read_kml(doc).collect();
+    let errors: Vec<_> = results.iter().filter_map(|r| r.as_ref().err()).map(|e| (e.kind(), &doc[e.span()])).collect();
+    assert_eq!(
+        vec![
+            (ParseErrorKind::Format, "1"),
+            (ParseErrorKind::InvalidNumber, "x,2"),
+            (ParseErrorKind::TooLong, "1,2,3,4"),
+            (ParseErrorKind::OutOfRange, "1,95"),
+            (ParseErrorKind::InvalidNumber, ",2"),
+        ],
+        errors
+    );
+    assert_eq!(7, results.len());
+    assert_eq!(Point::new(3., 4.), results[6].unwrap().point);
+
+    let line = write_kml(points[..4].iter().copied());
+    assert_eq!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2">
+<Placemark><LineString><coordinates>14.42,50.08,235 16.61,49.19 -79.982,40.446,0 151.2153,-33.8568</coordinates></LineString></Placemark>
+</kml>
+"#,
+        line
+    );
+    let read: Vec<PathPoint> = read_kml(&line).collect::<Result<_, _>>().unwrap();
+    assert_eq!(&points[..4], &read[..]);
+
+    assert!(write_kml(vec![Point::new(14.42, 50.08)]).contains("<Placemark><Point><coordinates>14.42,50.08</coordinates></Point></Placemark>"));
+    assert!(!write_kml(Vec::<Point<f64>>::new()).contains("Placemark"));
+}
@@ -0,0 +1,128 @@
+//! Transverse Mercator projection, using the Krüger series to the 4th order of the third flattening
+//! (accurate to well below a millimeter within the usual zone widths).
+
+use crate::datum::Ellipsoid;
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Transverse Mercator projection on an ellipsoid
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TransverseMercator {
+    pub ellipsoid: Ellipsoid,
+    /// Scale factor on the central meridian
+    pub k0: f64,
+    /// Central meridian (degrees)
+    pub lng0: f64,
+    /// Latitude of the origin (degrees)
+    pub lat0: f64,
+    /// False easting (m)
+    pub false_easting: f64,
+    /// False northing (m)
+    pub false_northing: f64,
+}
+
+impl TransverseMercator {
+    /// Third flattening
+    fn n(&self) -> f64 {
+        self.ellipsoid.f / (2. - self.ellipsoid.f)
+    }
+
+    /// Radius of the rectifying sphere, scaled by `k0`
+    fn radius(&self) -> f64 {
+        let n = self.n();
+        let n2 = n * n;
+        self.k0 * self.ellipsoid.a / (1. + n) * (1. + n2 / 4. + n2 * n2 / 64.)
+    }
+
+    /// Coefficients of the forward series
+    fn alpha(&self) -> [f64; 4] {
+        let n = self.n();
+        let (n2, n3, n4) = (n * n, n * n * n, n * n * n * n);
+        [
+            n / 2. - 2. / 3. * n2 + 5. / 16. * n3 + 41. / 180. * n4,
+            13. / 48. * n2 - 3. / 5. * n3 + 557. / 1440. * n4,
+            61. / 240. * n3 - 103. / 140. * n4,
+            49561. / 161280. * n4,
+        ]
+    }
+
+    /// Coefficients of the inverse series
+    fn beta(&self) -> [f64; 4] {
+        let n = self.n();
+        let (n2, n3, n4) = (n * n, n * n * n, n * n * n * n);
+        [
+            n / 2. - 2. / 3. * n2 + 37. / 96. * n3 - 1. / 360. * n4,
+            1. / 48. * n2 + 1. / 15. * n3 - 437. / 1440. * n4,
+            17. / 480. * n3 - 37. / 840. * n4,
+            4397. / 161280. * n4,
+        ]
+    }
+
+    /// Project latitude and longitude (degrees, on this ellipsoid) to unscaled `(ξ, η)`,
+    /// not shifted by the origin
+    fn xi_eta(&self, lat: f64, lng: f64) -> (f64, f64) {
+        let e = self.ellipsoid.e2().sqrt();
+        let phi = lat.to_radians();
+        let lambda = (lng - self.lng0).to_radians();
+
+        let sin_phi = phi.sin();
+        let t = (sin_phi.atanh() - e * (e * sin_phi).atanh()).sinh();
+        let xi_p = t.atan2(lambda.cos());
+        let eta_p = (lambda.sin() / (1. + t * t).sqrt()).atanh();
+
+        let mut xi = xi_p;
+        let mut eta = eta_p;
+        for (j, alpha) in self.alpha().iter().enumerate() {
+            let k = 2. * (j + 1) as f64;
+            xi += alpha * (k * xi_p).sin() * (k * eta_p).cosh();
+            eta += alpha * (k * xi_p).cos() * (k * eta_p).sinh();
+        }
+
+        (xi, eta)
+    }
+
+    /// Project latitude and longitude (degrees, on this ellipsoid) to easting and northing (m)
+    pub fn forward(&self, lat: f64, lng: f64) -> (f64, f64) {
+        let radius = self.radius();
+        let (xi, eta) = self.xi_eta(lat, lng);
+        let (xi0, _) = self.xi_eta(self.lat0, self.lng0);
+
+        (self.false_easting + radius * eta, self.false_northing + radius * (xi - xi0))
+    }
+
+    /// Get latitude and longitude (degrees, on this ellipsoid) of easting and northing (m)
+    pub fn inverse(&self, easting: f64, northing: f64) -> (f64, f64) {
+        let radius = self.radius();
+        let (xi0, _) = self.xi_eta(self.lat0, self.lng0);
+        let xi = (northing - self.false_northing) / radius + xi0;
+        let eta = (easting - self.false_easting) / radius;
+
+        let mut xi_p = xi;
+        let mut eta_p = eta;
+        for (j, beta) in self.beta().iter().enumerate() {
+            let k = 2. * (j + 1) as f64;
+            xi_p -= beta * (k * xi).sin() * (k * eta).cosh();
+            eta_p -= beta * (k * xi).cos() * (k * eta).sinh();
+        }
+
+        let tau_p = xi_p.sin() / (eta_p.sinh().powi(2) + xi_p.cos().powi(2)).sqrt();
+        let lambda = eta_p.sinh().atan2(xi_p.cos());
+
+        // conformal to geodetic latitude by Newton's method (Karney 2011)
+        let e2 = self.ellipsoid.e2();
+        let e = e2.sqrt();
+        let mut tau = tau_p;
+        for _ in 0..5 {
+            let sigma = (e * (e * tau / (1. + tau * tau).sqrt()).atanh()).sinh();
+            let tau_i = tau * (1. + sigma * sigma).sqrt() - sigma * (1. + tau * tau).sqrt();
+            let delta = (tau_p - tau_i) / (1. + tau_i * tau_i).sqrt() * (1. + (1. - e2) * tau * tau)
+                / ((1. - e2) * (1. + tau * tau).sqrt());
+            tau += delta;
+            if delta.abs() < 1e-14 {
+                break;
+            }
+        }
+
+        (tau.atan().to_degrees(), self.lng0 + lambda.to_degrees())
+    }
+}
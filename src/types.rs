@@ -0,0 +1,355 @@
+use crate::errors::{OutOfRange, ParseError};
+use crate::{parse, parse_lat, parse_lat_str, parse_lng, parse_lng_str, parse_str, GeoParseError, ParseOptions, Point};
+use core::convert::TryFrom;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Hemisphere, as written with a coordinate (`N`, `S`, `E`, `W`)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Hemisphere {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Hemisphere {
+    /// Check if this is the negative hemisphere (South or West)
+    pub fn is_negative(self) -> bool {
+        matches!(self, Hemisphere::South | Hemisphere::West)
+    }
+
+    /// Get the hemisphere letter
+    pub fn letter(self) -> char {
+        match self {
+            Hemisphere::North => 'N',
+            Hemisphere::South => 'S',
+            Hemisphere::East => 'E',
+            Hemisphere::West => 'W',
+        }
+    }
+}
+
+impl Display for Hemisphere {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+/// Coordinate split to whole degrees, whole minutes and seconds, with a hemisphere.
+///
+/// The `Display` implementation writes e.g. `40° 26′ 46″ N`. Precision (`{:.2}`) applies to seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dms {
+    pub degrees: u16,
+    pub minutes: u8,
+    pub seconds: f64,
+    pub hemisphere: Hemisphere,
+}
+
+impl Dms {
+    /// Split a decimal value, using `positive` or `negative` hemisphere based on its sign
+    fn from_decimal(value: f64, positive: Hemisphere, negative: Hemisphere) -> Dms {
+        let hemisphere = if value < 0. { negative } else { positive };
+        let value = value.abs();
+
+        let degrees = value.trunc();
+        let minutes = ((value - degrees) * 60.).trunc().min(59.);
+        let seconds = ((value - degrees) * 60. - minutes) * 60.;
+
+        Dms {
+            degrees: degrees as u16,
+            minutes: minutes as u8,
+            seconds,
+            hemisphere,
+        }
+    }
+
+    /// Get the signed decimal value in degrees
+    pub fn to_decimal(&self) -> f64 {
+        let value = f64::from(self.degrees) + f64::from(self.minutes) / 60. + self.seconds / 3600.;
+
+        if self.hemisphere.is_negative() {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+impl Display for Dms {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(precision) => {
+                // round seconds first, so that e.g. 59.999″ does not show as 60.00″
+                let scale = 10f64.powi(precision as i32);
+                let mut seconds = (self.seconds * scale).round() / scale;
+                let mut minutes = u32::from(self.minutes);
+                let mut degrees = u32::from(self.degrees);
+
+                if seconds >= 60. {
+                    seconds -= 60.;
+                    minutes += 1;
+                }
+
+                if minutes >= 60 {
+                    minutes -= 60;
+                    degrees += 1;
+                }
+
+                write!(f, "{}° {}′ {:.*}″ {}", degrees, minutes, precision, seconds, self.hemisphere)
+            }
+            None => write!(f, "{}° {}′ {}″ {}", self.degrees, self.minutes, self.seconds, self.hemisphere),
+        }
+    }
+}
+
+/// Write a value in decimal degrees with hemisphere, or as DMS in the alternate form
+fn write_coord(f: &mut Formatter<'_>, value: f64, positive: Hemisphere, negative: Hemisphere) -> fmt::Result {
+    if f.alternate() {
+        let dms = Dms::from_decimal(value, positive, negative);
+        return match f.precision() {
+            Some(precision) => write!(f, "{:.*}", precision, dms),
+            None => write!(f, "{}", dms),
+        };
+    }
+
+    let hemisphere = if value < 0. { negative } else { positive };
+    match f.precision() {
+        Some(precision) => write!(f, "{:.*}° {}", precision, value.abs(), hemisphere),
+        None => write!(f, "{}° {}", value.abs(), hemisphere),
+    }
+}
+
+/// Latitude in degrees, guaranteed to be within ±90°. Positive latitude is North.
+///
+/// The `Display` implementation writes decimal degrees with hemisphere (e.g. `40.446° N`),
+/// which can be parsed back. The alternate form (`{:#}`) writes degrees, minutes and seconds.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Latitude(f64);
+
+impl Latitude {
+    /// Create from decimal degrees. Returns `None` if out of range or not finite.
+    pub fn new(degrees: f64) -> Option<Latitude> {
+        if (-90f64..=90f64).contains(&degrees) {
+            Some(Latitude(degrees))
+        } else {
+            None
+        }
+    }
+
+    /// Get the value in decimal degrees
+    pub fn degrees(self) -> f64 {
+        self.0
+    }
+
+    /// Split to degrees, minutes and seconds
+    pub fn to_dms(self) -> Dms {
+        Dms::from_decimal(self.0, Hemisphere::North, Hemisphere::South)
+    }
+}
+
+/// Longitude in degrees, guaranteed to be within ±180°. Positive longitude is East.
+///
+/// The `Display` implementation writes decimal degrees with hemisphere (e.g. `79.982° W`),
+/// which can be parsed back. The alternate form (`{:#}`) writes degrees, minutes and seconds.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Longitude(f64);
+
+impl Longitude {
+    /// Create from decimal degrees. Returns `None` if out of range or not finite.
+    pub fn new(degrees: f64) -> Option<Longitude> {
+        if (-180f64..=180f64).contains(&degrees) {
+            Some(Longitude(degrees))
+        } else {
+            None
+        }
+    }
+
+    /// Get the value in decimal degrees
+    pub fn degrees(self) -> f64 {
+        self.0
+    }
+
+    /// Split to degrees, minutes and seconds
+    pub fn to_dms(self) -> Dms {
+        Dms::from_decimal(self.0, Hemisphere::East, Hemisphere::West)
+    }
+}
+
+impl From<Latitude> for f64 {
+    fn from(lat: Latitude) -> Self {
+        lat.0
+    }
+}
+
+impl From<Longitude> for f64 {
+    fn from(lng: Longitude) -> Self {
+        lng.0
+    }
+}
+
+impl TryFrom<f64> for Latitude {
+    type Error = OutOfRange;
+
+    fn try_from(value: f64) -> Result<Self, OutOfRange> {
+        Latitude::new(value).ok_or(OutOfRange)
+    }
+}
+
+impl TryFrom<f64> for Longitude {
+    type Error = OutOfRange;
+
+    fn try_from(value: f64) -> Result<Self, OutOfRange> {
+        Longitude::new(value).ok_or(OutOfRange)
+    }
+}
+
+impl From<Latitude> for Dms {
+    fn from(lat: Latitude) -> Self {
+        lat.to_dms()
+    }
+}
+
+impl From<Longitude> for Dms {
+    fn from(lng: Longitude) -> Self {
+        lng.to_dms()
+    }
+}
+
+/// Fails if the hemisphere is not North or South, or the value is out of range
+impl TryFrom<Dms> for Latitude {
+    type Error = OutOfRange;
+
+    fn try_from(dms: Dms) -> Result<Self, OutOfRange> {
+        match dms.hemisphere {
+            Hemisphere::North | Hemisphere::South => Latitude::new(dms.to_decimal()).ok_or(OutOfRange),
+            _ => Err(OutOfRange),
+        }
+    }
+}
+
+/// Fails if the hemisphere is not East or West, or the value is out of range
+impl TryFrom<Dms> for Longitude {
+    type Error = OutOfRange;
+
+    fn try_from(dms: Dms) -> Result<Self, OutOfRange> {
+        match dms.hemisphere {
+            Hemisphere::East | Hemisphere::West => Longitude::new(dms.to_decimal()).ok_or(OutOfRange),
+            _ => Err(OutOfRange),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Latitude {
+    type Error = GeoParseError<&'a str>;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        parse_lat(value).map(Latitude)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Longitude {
+    type Error = GeoParseError<&'a str>;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        parse_lng(value).map(Longitude)
+    }
+}
+
+impl FromStr for Latitude {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_lat_str(s, &ParseOptions::default()).map(Latitude)
+    }
+}
+
+impl FromStr for Longitude {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_lng_str(s, &ParseOptions::default()).map(Longitude)
+    }
+}
+
+impl Display for Latitude {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_coord(f, self.0, Hemisphere::North, Hemisphere::South)
+    }
+}
+
+impl Display for Longitude {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_coord(f, self.0, Hemisphere::East, Hemisphere::West)
+    }
+}
+
+/// Coordinate pair (latitude, longitude) in decimal degrees.
+///
+/// This type implements `FromStr` using `parse_str()`, so it can be used with anything built on it
+/// (e.g. `"40.446,-79.982".parse::<LatLon>()`).
+///
+/// The `Display` implementation writes signed decimal degrees, e.g. `40.446,-79.982`, which can be
+/// parsed back to the same value as long as it is in range. Precision (`{:.5}`) applies to both numbers.
+/// The alternate form (`{:#}`) writes degrees, minutes and seconds with hemispheres.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatLon {
+    /// Latitude, positive is North
+    pub lat: f64,
+    /// Longitude, positive is East
+    pub lng: f64,
+}
+
+impl LatLon {
+    /// Create from latitude and longitude (no range checking is done)
+    pub fn new(lat: f64, lng: f64) -> LatLon {
+        LatLon { lat, lng }
+    }
+}
+
+impl From<LatLon> for Point<f64> {
+    fn from(ll: LatLon) -> Self {
+        Point::new(ll.lng, ll.lat)
+    }
+}
+
+impl From<Point<f64>> for LatLon {
+    fn from(p: Point<f64>) -> Self {
+        LatLon { lat: p.y(), lng: p.x() }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for LatLon {
+    type Error = GeoParseError<&'a str>;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        parse(value).map(LatLon::from)
+    }
+}
+
+impl FromStr for LatLon {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_str(s, &ParseOptions::default()).map(LatLon::from)
+    }
+}
+
+impl Display for LatLon {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write_coord(f, self.lat, Hemisphere::North, Hemisphere::South)?;
+            f.write_str(", ")?;
+            return write_coord(f, self.lng, Hemisphere::East, Hemisphere::West);
+        }
+
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*},{:.*}", precision, self.lat, precision, self.lng),
+            None => write!(f, "{},{}", self.lat, self.lng),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use core::f64::consts::PI;
+
+/// Angular unit of a decimal coordinate
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum AngleUnit {
+    /// Degrees (`°`), 360 per full circle
+    #[default]
+    Degrees,
+    /// Radians (`rad`), 2π per full circle
+    Radians,
+    /// Gradians (`gon`, `grad` or `g`), 400 per full circle
+    Gradians,
+    /// NATO mils (`mil` or `mils`), 6400 per full circle
+    Mils,
+}
+
+impl AngleUnit {
+    /// Recognize a unit suffix
+    pub(crate) fn from_suffix(suffix: &str) -> Option<AngleUnit> {
+        match suffix {
+            "°" => Some(AngleUnit::Degrees),
+            "rad" => Some(AngleUnit::Radians),
+            "gon" | "grad" | "g" => Some(AngleUnit::Gradians),
+            "mil" | "mils" => Some(AngleUnit::Mils),
+            _ => None,
+        }
+    }
+
+    /// Convert a value in this unit to degrees
+    pub fn to_degrees(self, value: f64) -> f64 {
+        match self {
+            AngleUnit::Degrees => value,
+            AngleUnit::Radians => value * (180f64 / PI),
+            AngleUnit::Gradians => value * (360f64 / 400f64),
+            AngleUnit::Mils => value * (360f64 / 6400f64),
+        }
+    }
+
+    /// Convert a value in degrees to this unit
+    pub fn from_degrees(self, degrees: f64) -> f64 {
+        match self {
+            AngleUnit::Degrees => degrees,
+            AngleUnit::Radians => degrees * (PI / 180f64),
+            AngleUnit::Gradians => degrees * (400f64 / 360f64),
+            AngleUnit::Mils => degrees * (6400f64 / 360f64),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Universal Polar Stereographic grid (WGS84), covering the polar regions outside UTM
+
+use crate::datum::Ellipsoid;
+use crate::{grid, GeoParseError, Point};
+use core::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+use core::fmt;
+use core::fmt::{Display, Formatter};
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Scale factor at the pole
+const K0: f64 = 0.994;
+/// False easting and northing (m)
+const FALSE_ORIGIN: f64 = 2_000_000.;
+/// Latitude limits of the grid: UPS is used north of 84° N and south of 80° S
+const NORTH_LIMIT: f64 = 84.;
+const SOUTH_LIMIT: f64 = -80.;
+/// Overlap with UTM accepted when parsing (degrees)
+const OVERLAP: f64 = 0.5;
+
+/// Position in the Universal Polar Stereographic grid, e.g. `Z 2426773 1530125`.
+///
+/// Zones are `A` (south, western longitudes), `B` (south, eastern), `Y` (north, western)
+/// and `Z` (north, eastern).
+///
+/// The `Display` implementation writes the zone, easting and northing. Precision (`{:.2}`) applies to
+/// easting and northing, which are rounded to meters by default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ups {
+    /// Zone letter, `A`, `B`, `Y` or `Z`
+    pub zone: char,
+    /// Easting (m), with the false easting of 2000 km
+    pub easting: f64,
+    /// Northing (m), with the false northing of 2000 km
+    pub northing: f64,
+}
+
+impl Ups {
+    /// Project a point (WGS84). Returns `None` outside the UPS latitudes (north of 84° N, south of 80° S)
+    /// or if longitude is out of range. The limits themselves belong to UTM.
+    pub fn from_point(point: Point<f64>) -> Option<Ups> {
+        let (lat, lng) = (point.y(), point.x());
+        let north = lat > NORTH_LIMIT && lat <= 90.;
+        if !(north || (-90.0..SOUTH_LIMIT).contains(&lat)) || !(-180f64..=180f64).contains(&lng) {
+            return None;
+        }
+
+        let rho = radius(lat.abs());
+        let lambda = lng.to_radians();
+        let easting = FALSE_ORIGIN + rho * lambda.sin();
+        let northing = if north {
+            FALSE_ORIGIN - rho * lambda.cos()
+        } else {
+            FALSE_ORIGIN + rho * lambda.cos()
+        };
+
+        Some(Ups {
+            zone: zone_letter(north, easting),
+            easting,
+            northing,
+        })
+    }
+
+    /// Is the position in the northern zones (`Y`, `Z`)
+    pub fn is_north(&self) -> bool {
+        matches!(self.zone, 'Y' | 'Z')
+    }
+
+    /// Get the WGS84 point
+    pub fn to_point(&self) -> Point<f64> {
+        let (dx, dy) = (self.easting - FALSE_ORIGIN, self.northing - FALSE_ORIGIN);
+        let e = Ellipsoid::WGS84.e2().sqrt();
+
+        let t = dx.hypot(dy) / (2. * Ellipsoid::WGS84.a * K0 / c());
+        let mut phi = FRAC_PI_2 - 2. * t.atan();
+        for _ in 0..10 {
+            let esin = e * phi.sin();
+            let next = FRAC_PI_2 - 2. * (t * ((1. - esin) / (1. + esin)).powf(e / 2.)).atan();
+            let done = (next - phi).abs() < 1e-12;
+            phi = next;
+            if done {
+                break;
+            }
+        }
+
+        if dx == 0. && dy == 0. {
+            // longitude of the pole is arbitrary
+            Point::new(0., if self.is_north() { 90. } else { -90. })
+        } else if self.is_north() {
+            Point::new(dx.atan2(-dy).to_degrees(), phi.to_degrees())
+        } else {
+            Point::new(dx.atan2(dy).to_degrees(), -phi.to_degrees())
+        }
+    }
+
+    /// Parse a UPS position: the zone letter followed by easting and northing, e.g. `Z 2426773 1530125`.
+    ///
+    /// The zone must match the side of the easting, and the position must be in the polar region
+    /// of the zone (with a 0.5° overlap with UTM).
+    pub fn parse<T: AsRef<str> + Display>(text: T) -> Result<Ups, GeoParseError<T>> {
+        match do_parse_ups(text.as_ref()) {
+            Some(p) => Ok(p),
+            None => Err(GeoParseError(text)),
+        }
+    }
+}
+
+/// Square root of `(1 + e)^(1 + e) · (1 - e)^(1 - e)`
+fn c() -> f64 {
+    let e = Ellipsoid::WGS84.e2().sqrt();
+    ((1. + e).powf(1. + e) * (1. - e).powf(1. - e)).sqrt()
+}
+
+/// Distance from the pole in the grid (m) of a latitude (degrees, positive)
+fn radius(lat: f64) -> f64 {
+    let e = Ellipsoid::WGS84.e2().sqrt();
+    let phi = lat.to_radians();
+    let esin = e * phi.sin();
+    let t = (FRAC_PI_4 - phi / 2.).tan() / ((1. - esin) / (1. + esin)).powf(e / 2.);
+    2. * Ellipsoid::WGS84.a * K0 * t / c()
+}
+
+fn zone_letter(north: bool, easting: f64) -> char {
+    match (north, easting < FALSE_ORIGIN) {
+        (true, true) => 'Y',
+        (true, false) => 'Z',
+        (false, true) => 'A',
+        (false, false) => 'B',
+    }
+}
+
+fn do_parse_ups(s: &str) -> Option<Ups> {
+    let s = s.trim();
+    let zone = s.chars().next()?.to_ascii_uppercase();
+    if !matches!(zone, 'A' | 'B' | 'Y' | 'Z') {
+        return None;
+    }
+
+    let (easting, northing) = grid::parse_pair(&s[1..])?;
+    let ups = Ups {
+        zone,
+        easting,
+        northing,
+    };
+
+    let lat = ups.to_point().y();
+    let in_region = if ups.is_north() {
+        lat >= NORTH_LIMIT - OVERLAP
+    } else {
+        lat <= SOUTH_LIMIT + OVERLAP
+    };
+    if !in_region || zone_letter(ups.is_north(), easting) != zone {
+        return None;
+    }
+
+    Some(ups)
+}
+
+impl From<Ups> for Point<f64> {
+    fn from(ups: Ups) -> Self {
+        ups.to_point()
+    }
+}
+
+impl Display for Ups {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(0);
+        write!(
+            f,
+            "{} {:.*} {:.*}",
+            self.zone, precision, self.easting, precision, self.northing
+        )
+    }
+}
@@ -0,0 +1,157 @@
+//! UTM and MGRS grid references (WGS84)
+
+use crate::datum::Ellipsoid;
+use crate::tm::TransverseMercator;
+use crate::Point;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+/// Latitude bands from 80° S, 8° each (X is 12°)
+const BANDS: &[u8; 20] = b"CDEFGHJKLMNPQRSTUVWX";
+/// MGRS 100 km column letters, by zone modulo 3
+const MGRS_COLUMNS: [&[u8; 8]; 3] = [b"ABCDEFGH", b"JKLMNPQR", b"STUVWXYZ"];
+/// MGRS 100 km row letters
+const MGRS_ROWS: &[u8; 20] = b"ABCDEFGHJKLMNPQRSTUV";
+
+/// Position in the Universal Transverse Mercator grid.
+///
+/// The `Display` implementation writes e.g. `17T 586329 4477758`. Precision (`{:.2}`) applies to
+/// easting and northing, which are rounded to meters by default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Utm {
+    /// Zone number, 1 to 60
+    pub zone: u8,
+    /// Latitude band letter, `C` to `X`
+    pub band: char,
+    /// Easting (m), with the false easting of 500 km
+    pub easting: f64,
+    /// Northing (m), with the false northing of 10 000 km in the southern hemisphere
+    pub northing: f64,
+}
+
+impl Utm {
+    /// Project a point (WGS84). Returns `None` outside the UTM latitudes (80° S to 84° N)
+    /// or if longitude is out of range.
+    ///
+    /// The exceptions of zones around Norway and Svalbard are applied.
+    pub fn from_point(point: Point<f64>) -> Option<Utm> {
+        let (lat, lng) = (point.y(), point.x());
+        if !(-80f64..=84f64).contains(&lat) || !(-180f64..=180f64).contains(&lng) {
+            return None;
+        }
+
+        let band = BANDS[(((lat + 80.) / 8.).floor() as usize).min(BANDS.len() - 1)];
+        let mut zone = ((((lng + 180.) / 6.).floor() as u8) + 1).min(60);
+
+        if band == b'V' && (3f64..12f64).contains(&lng) {
+            zone = 32;
+        } else if band == b'X' && (0f64..42f64).contains(&lng) {
+            zone = match lng {
+                lng if lng < 9. => 31,
+                lng if lng < 21. => 33,
+                lng if lng < 33. => 35,
+                _ => 37,
+            };
+        }
+
+        let tm = TransverseMercator {
+            ellipsoid: Ellipsoid::WGS84,
+            k0: 0.9996,
+            lng0: f64::from(zone) * 6. - 183.,
+            lat0: 0.,
+            false_easting: 500_000.,
+            false_northing: if lat < 0. { 10_000_000. } else { 0. },
+        };
+
+        let (easting, northing) = tm.forward(lat, lng);
+
+        Some(Utm {
+            zone,
+            band: char::from(band),
+            easting,
+            northing,
+        })
+    }
+
+    /// Get the MGRS grid reference of this position
+    pub fn to_mgrs(&self) -> Mgrs {
+        // MGRS truncates the coordinates to the reference precision
+        let easting = self.easting.floor().max(0.) as u32;
+        let northing = self.northing.floor().max(0.) as u32;
+
+        let set = usize::from((self.zone - 1) % 3);
+        let columns = MGRS_COLUMNS[set];
+        let column = columns[((easting / 100_000) as usize).saturating_sub(1).min(columns.len() - 1)];
+
+        let offset = if self.zone % 2 == 0 { 5 } else { 0 };
+        let row = MGRS_ROWS[(northing / 100_000 + offset) as usize % MGRS_ROWS.len()];
+
+        Mgrs {
+            zone: self.zone,
+            band: self.band,
+            column: char::from(column),
+            row: char::from(row),
+            easting: easting % 100_000,
+            northing: northing % 100_000,
+        }
+    }
+}
+
+impl Display for Utm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(0);
+        write!(
+            f,
+            "{}{} {:.*} {:.*}",
+            self.zone, self.band, precision, self.easting, precision, self.northing
+        )
+    }
+}
+
+/// Military Grid Reference System position, e.g. `17TNE8632877758`.
+///
+/// The `Display` implementation writes the reference to 1 m. Precision (`{:.3}`) sets the number of digits
+/// of easting and northing (0 to 5), e.g. `17TNE863777` for 100 m.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Mgrs {
+    /// UTM zone number, 1 to 60
+    pub zone: u8,
+    /// Latitude band letter, `C` to `X`
+    pub band: char,
+    /// 100 km square column letter
+    pub column: char,
+    /// 100 km square row letter
+    pub row: char,
+    /// Easting within the 100 km square (m)
+    pub easting: u32,
+    /// Northing within the 100 km square (m)
+    pub northing: u32,
+}
+
+impl Mgrs {
+    /// Get the MGRS grid reference of a point (WGS84). Returns `None` outside the UTM latitudes.
+    pub fn from_point(point: Point<f64>) -> Option<Mgrs> {
+        Utm::from_point(point).map(|utm| utm.to_mgrs())
+    }
+}
+
+impl Display for Mgrs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let digits = f.precision().unwrap_or(5).min(5);
+        let divisor = 10u32.pow(5 - digits as u32);
+
+        write!(f, "{}{}{}{}", self.zone, self.band, self.column, self.row)?;
+        if digits > 0 {
+            write!(
+                f,
+                "{:0w$}{:0w$}",
+                self.easting / divisor,
+                self.northing / divisor,
+                w = digits
+            )?;
+        }
+        Ok(())
+    }
+}
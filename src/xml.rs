@@ -0,0 +1,130 @@
+//! Minimal XML scanning for the GPX and KML readers: tags, attributes and element text,
+//! without entities or validation
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// Start, end or empty-element tag
+pub(crate) struct Tag<'a> {
+    /// Name without the namespace prefix
+    pub name: &'a str,
+    /// Is it an end tag (`</name>`)
+    pub closing: bool,
+    /// Is it an empty-element tag (`<name/>`)
+    pub empty: bool,
+    /// Byte offset of `<`
+    pub start: usize,
+    /// Byte offset after `>`
+    pub end: usize,
+    /// Byte offset of the attributes
+    attrs_start: usize,
+    attrs: &'a str,
+}
+
+impl<'a> Tag<'a> {
+    /// Find an attribute by name, returning its value and the byte offset of the value
+    pub fn attribute(&self, name: &str) -> Option<(&'a str, usize)> {
+        let mut rest = self.attrs;
+        loop {
+            let trimmed = rest.trim_start();
+            let eq = trimmed.find('=')?;
+            let attr = trimmed[..eq].trim_end();
+            let value = trimmed[eq + 1..].trim_start();
+            let quote = value.chars().next().filter(|&c| c == '"' || c == '\'')?;
+            let len = value[1..].find(quote)?;
+
+            if attr == name {
+                let offset = self.attrs_start + (self.attrs.len() - value.len()) + 1;
+                return Some((&value[1..=len], offset));
+            }
+            rest = &value[len + 2..];
+        }
+    }
+}
+
+/// Find the next tag from the byte offset, skipping comments, CDATA, declarations and
+/// processing instructions
+pub(crate) fn next_tag(doc: &str, from: usize) -> Option<Tag<'_>> {
+    let mut pos = from;
+    loop {
+        let start = pos + doc[pos..].find('<')?;
+        let rest = &doc[start..];
+
+        let skip_to = |terminator: &str| rest.find(terminator).map(|i| start + i + terminator.len());
+        if rest.starts_with("<!--") {
+            pos = skip_to("-->")?;
+            continue;
+        } else if rest.starts_with("<![CDATA[") {
+            pos = skip_to("]]>")?;
+            continue;
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            pos = skip_to(">")?;
+            continue;
+        }
+
+        let closing = rest[1..].starts_with('/');
+        let name_start = start + if closing { 2 } else { 1 };
+        let name_len = doc[name_start..]
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+            .unwrap_or(doc.len() - name_start);
+        let name_end = name_start + name_len;
+
+        // `>` may appear in quoted attribute values
+        let mut quote = None;
+        let gt = doc[name_end..].char_indices().find_map(|(i, c)| {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None if c == '>' => return Some(name_end + i),
+                None => {}
+            }
+            None
+        })?;
+
+        let empty = doc[..gt].ends_with('/');
+        let attrs_end = if empty { gt - 1 } else { gt };
+        let name = &doc[name_start..name_end];
+        return Some(Tag {
+            name: name.rsplit(':').next().unwrap_or(name),
+            closing,
+            empty,
+            start,
+            end: gt + 1,
+            attrs_start: name_end,
+            attrs: &doc[name_end..attrs_end.max(name_end)],
+        });
+    }
+}
+
+/// Get the text following a start tag, up to the next tag, with its byte offset
+pub(crate) fn text<'a>(doc: &'a str, tag: &Tag<'_>) -> (&'a str, usize) {
+    let len = doc[tag.end..].find('<').unwrap_or(doc.len() - tag.end);
+    let raw = &doc[tag.end..tag.end + len];
+    let trimmed = raw.trim_start();
+    (trimmed.trim_end(), tag.end + raw.len() - trimmed.len())
+}
+
+/// Parse an XML decimal number (no `NaN`, `inf` or decimal comma)
+pub(crate) fn parse_number(s: &str) -> Option<f64> {
+    let valid = !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b));
+    if valid {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Append text with the XML special characters escaped
+#[cfg(feature = "alloc")]
+pub(crate) fn push_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}